@@ -0,0 +1,232 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contractevent, contractimpl, contracttype, symbol_short, token, xdr::ToXdr, Address,
+    Bytes, BytesN, Env, Symbol, Vec,
+};
+
+// Storage keys
+const ADMIN: Symbol = symbol_short!("admin");
+const TOKEN: Symbol = symbol_short!("token");
+const MODE: Symbol = symbol_short!("mode");
+const RELAYERS: Symbol = symbol_short!("relayers");
+const THRESHOLD: Symbol = symbol_short!("threshold");
+const NONCE: Symbol = symbol_short!("nonce");
+
+// Nonce yang sudah diproses disimpan ~30 hari, diperpanjang saat sisa TTL < ~29 hari
+const PROCESSED_TTL_THRESHOLD: u32 = 500_000;
+const PROCESSED_TTL_EXTEND: u32 = 518_400;
+
+// Key persistent storage: satu entry per nonce, supaya biaya bridge_in tidak
+// ikut membesar bersama jumlah transfer yang pernah diproses
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Processed(u64),
+}
+
+// Peran bridge untuk token-nya: sisi Lock memegang token asli di escrow,
+// sisi Mint mencetak dan membakar representasinya
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BridgeMode {
+    Lock,
+    Mint,
+}
+
+// Tanda tangan ed25519 satu relayer atas pesan bridge
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayerSignature {
+    pub public_key: BytesN<32>,
+    pub signature: BytesN<64>,
+}
+
+// Token dikunci (Lock) atau dibakar (Mint) di sisi ini; relayer meneruskan
+// event ini ke bridge di sisi seberang
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeOut {
+    #[topic]
+    pub nonce: u64,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+// Token dilepas dari escrow (Lock) atau dicetak (Mint) setelah quorum
+// relayer tercapai
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeIn {
+    #[topic]
+    pub nonce: u64,
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contract]
+pub struct BridgeContract;
+
+#[contractimpl]
+impl BridgeContract {
+
+    // Initialize bridge untuk satu token. Di sisi Mint, bridge harus
+    // menjadi admin token supaya bisa mint.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        mode: BridgeMode,
+        relayers: Vec<BytesN<32>>,
+        threshold: u32,
+    ) {
+        admin.require_auth();
+
+        if env.storage().instance().has(&ADMIN) {
+            panic!("Bridge sudah diinisialisasi");
+        }
+
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&TOKEN, &token);
+        env.storage().instance().set(&MODE, &mode);
+        Self::write_relayers(&env, relayers, threshold);
+    }
+
+    // Admin mengganti daftar relayer dan quorum
+    pub fn set_relayers(env: Env, relayers: Vec<BytesN<32>>, threshold: u32) {
+        let admin: Address = env.storage().instance().get(&ADMIN).unwrap();
+        admin.require_auth();
+
+        Self::write_relayers(&env, relayers, threshold);
+    }
+
+    // Kunci (Lock) atau bakar (Mint) token milik `from` dan emit event
+    // bridge_out dengan nonce baru
+    pub fn bridge_out(env: Env, from: Address, to: Address, amount: i128) -> u64 {
+        from.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount harus lebih dari 0");
+        }
+
+        let token_address: Address = env.storage().instance().get(&TOKEN).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        let contract_address = env.current_contract_address();
+        match Self::get_mode(env.clone()) {
+            BridgeMode::Lock => token_client.transfer(&from, &contract_address, &amount),
+            BridgeMode::Mint => token_client.burn(&from, &amount),
+        }
+
+        let nonce: u64 = env.storage().instance().get(&NONCE).unwrap_or(0);
+        env.storage().instance().set(&NONCE, &(nonce + 1));
+
+        BridgeOut { nonce, from, to, amount }.publish(&env);
+
+        nonce
+    }
+
+    // Lepas (Lock) atau cetak (Mint) token kalau cukup relayer menandatangani
+    // pesan untuk nonce dari sisi seberang. Setiap nonce hanya bisa diproses sekali.
+    pub fn bridge_in(
+        env: Env,
+        nonce: u64,
+        to: Address,
+        amount: i128,
+        signatures: Vec<RelayerSignature>,
+    ) {
+        if amount <= 0 {
+            panic!("Amount harus lebih dari 0");
+        }
+
+        if Self::is_processed(env.clone(), nonce) {
+            panic!("Nonce sudah diproses");
+        }
+
+        let relayers: Vec<BytesN<32>> = env.storage().instance().get(&RELAYERS).unwrap();
+        let threshold: u32 = env.storage().instance().get(&THRESHOLD).unwrap();
+        let message = Self::bridge_message(env.clone(), nonce, to.clone(), amount);
+
+        // ed25519_verify panic kalau signature tidak valid
+        let mut signers: Vec<BytesN<32>> = Vec::new(&env);
+        for sig in signatures.iter() {
+            if !relayers.contains(&sig.public_key) {
+                panic!("Relayer tidak terdaftar");
+            }
+            if signers.contains(&sig.public_key) {
+                panic!("Signature relayer duplikat");
+            }
+            env.crypto().ed25519_verify(&sig.public_key, &message, &sig.signature);
+            signers.push_back(sig.public_key);
+        }
+
+        if signers.len() < threshold {
+            panic!("Quorum relayer belum tercapai");
+        }
+
+        let key = DataKey::Processed(nonce);
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, PROCESSED_TTL_THRESHOLD, PROCESSED_TTL_EXTEND);
+
+        let token_address: Address = env.storage().instance().get(&TOKEN).unwrap();
+        match Self::get_mode(env.clone()) {
+            BridgeMode::Lock => {
+                let token_client = token::Client::new(&env, &token_address);
+                let contract_address = env.current_contract_address();
+                token_client.transfer(&contract_address, &to, &amount);
+            }
+            BridgeMode::Mint => {
+                let token_client = token::StellarAssetClient::new(&env, &token_address);
+                token_client.mint(&to, &amount);
+            }
+        }
+
+        BridgeIn { nonce, to, amount }.publish(&env);
+    }
+
+    // Get pesan yang harus ditandatangani relayer untuk bridge_in di bridge ini
+    pub fn bridge_message(env: Env, nonce: u64, to: Address, amount: i128) -> Bytes {
+        (env.current_contract_address(), nonce, to, amount).to_xdr(&env)
+    }
+
+    // Get apakah nonce sudah diproses di sisi tujuan
+    pub fn is_processed(env: Env, nonce: u64) -> bool {
+        env.storage().persistent().has(&DataKey::Processed(nonce))
+    }
+
+    // Get peran bridge untuk token-nya
+    pub fn get_mode(env: Env) -> BridgeMode {
+        env.storage().instance().get(&MODE).unwrap()
+    }
+
+    // Get nonce berikutnya untuk bridge_out
+    pub fn get_nonce(env: Env) -> u64 {
+        env.storage().instance().get(&NONCE).unwrap_or(0)
+    }
+
+    // Get daftar public key relayer
+    pub fn get_relayers(env: Env) -> Vec<BytesN<32>> {
+        env.storage().instance().get(&RELAYERS).unwrap_or(Vec::new(&env))
+    }
+
+    // Get jumlah signature minimum untuk bridge_in
+    pub fn get_threshold(env: Env) -> u32 {
+        env.storage().instance().get(&THRESHOLD).unwrap_or(0)
+    }
+
+    fn write_relayers(env: &Env, relayers: Vec<BytesN<32>>, threshold: u32) {
+        if threshold == 0 || threshold > relayers.len() {
+            panic!("Threshold tidak valid");
+        }
+        for (i, relayer) in relayers.iter().enumerate() {
+            if relayers.last_index_of(&relayer) != Some(i as u32) {
+                panic!("Relayer duplikat");
+            }
+        }
+
+        env.storage().instance().set(&RELAYERS, &relayers);
+        env.storage().instance().set(&THRESHOLD, &threshold);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,304 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    events::Event,
+    testutils::{Address as _, Events},
+    token, vec, Address, Env, String,
+};
+use token_contract::{TokenContract, TokenContractClient};
+
+// Both sides of the bridge living in one test Env
+struct BridgeSetup<'a> {
+    source_token: TokenContractClient<'a>,
+    dest_token: token::Client<'a>,
+    source_bridge: BridgeContractClient<'a>,
+    dest_bridge: BridgeContractClient<'a>,
+    relayer_keys: std::vec::Vec<SigningKey>,
+    user: Address,
+}
+
+fn setup_bridge<'a>(env: &Env) -> BridgeSetup<'a> {
+    let admin = Address::generate(env);
+    let user = Address::generate(env);
+
+    // 3 relayers, 2 signatures needed
+    let relayer_keys: std::vec::Vec<SigningKey> =
+        (1u8..=3).map(|i| SigningKey::from_bytes(&[i; 32])).collect();
+    let mut relayers = Vec::new(env);
+    for key in relayer_keys.iter() {
+        relayers.push_back(BytesN::from_array(env, &key.verifying_key().to_bytes()));
+    }
+
+    let name = String::from_str(env, "Bridged Token");
+    let symbol = String::from_str(env, "BRG");
+
+    let source_token = TokenContractClient::new(env, &env.register(TokenContract, ()));
+    source_token.initialize(&user, &name, &symbol, &1_000_000);
+    let source_bridge = BridgeContractClient::new(env, &env.register(BridgeContract, ()));
+    source_bridge.initialize(&admin, &source_token.address, &BridgeMode::Lock, &relayers, &2);
+
+    // Destination token starts empty and is minted and burned by its bridge
+    let dest_bridge = BridgeContractClient::new(env, &env.register(BridgeContract, ()));
+    let dest_asset = env.register_stellar_asset_contract_v2(dest_bridge.address.clone());
+    let dest_token = token::Client::new(env, &dest_asset.address());
+    dest_bridge.initialize(&admin, &dest_token.address, &BridgeMode::Mint, &relayers, &2);
+
+    BridgeSetup { source_token, dest_token, source_bridge, dest_bridge, relayer_keys, user }
+}
+
+fn sign(env: &Env, key: &SigningKey, message: &Bytes) -> RelayerSignature {
+    let bytes: std::vec::Vec<u8> = message.iter().collect();
+    RelayerSignature {
+        public_key: BytesN::from_array(env, &key.verifying_key().to_bytes()),
+        signature: BytesN::from_array(env, &key.sign(&bytes).to_bytes()),
+    }
+}
+
+#[test]
+fn test_bridge_out_and_in() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_bridge(&env);
+    let recipient = Address::generate(&env);
+
+    // Lock on the source side
+    let nonce = s.source_bridge.bridge_out(&s.user, &recipient, &40_000);
+    let event = BridgeOut { nonce, from: s.user.clone(), to: recipient.clone(), amount: 40_000 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (s.source_bridge.address.clone(), event.topics(&env), event.data(&env))]
+    );
+
+    assert_eq!(nonce, 0);
+    assert_eq!(s.source_token.balance(&s.user), 960_000);
+    assert_eq!(s.source_token.balance(&s.source_bridge.address), 40_000);
+
+    // Relayers attest on the destination side
+    let message = s.dest_bridge.bridge_message(&nonce, &recipient, &40_000);
+    let signatures = vec![
+        &env,
+        sign(&env, &s.relayer_keys[0], &message),
+        sign(&env, &s.relayer_keys[2], &message),
+    ];
+    s.dest_bridge.bridge_in(&nonce, &recipient, &40_000, &signatures);
+
+    assert_eq!(s.dest_token.balance(&recipient), 40_000);
+    assert!(s.dest_bridge.is_processed(&nonce));
+    assert_eq!(s.source_bridge.get_nonce(), 1);
+}
+
+#[test]
+fn test_bridge_round_trip() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_bridge(&env);
+    let recipient = Address::generate(&env);
+
+    // Source -> destination: locked on the source, minted on the destination
+    let out_nonce = s.source_bridge.bridge_out(&s.user, &recipient, &40_000);
+    let message = s.dest_bridge.bridge_message(&out_nonce, &recipient, &40_000);
+    let signatures = vec![
+        &env,
+        sign(&env, &s.relayer_keys[0], &message),
+        sign(&env, &s.relayer_keys[1], &message),
+    ];
+    s.dest_bridge.bridge_in(&out_nonce, &recipient, &40_000, &signatures);
+
+    // Destination -> source: burned on the destination, released from escrow
+    let back_nonce = s.dest_bridge.bridge_out(&recipient, &s.user, &15_000);
+    assert_eq!(s.dest_token.balance(&recipient), 25_000);
+    assert_eq!(s.dest_token.balance(&s.dest_bridge.address), 0);
+
+    let message = s.source_bridge.bridge_message(&back_nonce, &s.user, &15_000);
+    let signatures = vec![
+        &env,
+        sign(&env, &s.relayer_keys[1], &message),
+        sign(&env, &s.relayer_keys[2], &message),
+    ];
+    s.source_bridge.bridge_in(&back_nonce, &s.user, &15_000, &signatures);
+
+    let event = BridgeIn { nonce: back_nonce, to: s.user.clone(), amount: 15_000 };
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![&env, (s.source_bridge.address.clone(), event.topics(&env), event.data(&env))]
+    );
+
+    // Escrow on the source backs exactly what is still out on the destination
+    assert_eq!(s.source_token.balance(&s.user), 975_000);
+    assert_eq!(s.source_token.balance(&s.source_bridge.address), 25_000);
+    assert_eq!(s.source_token.get_total_supply(), 1_000_000);
+    assert!(s.source_bridge.is_processed(&back_nonce));
+}
+
+#[test]
+fn test_bridge_round_trip_token_contract_destination() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_bridge(&env);
+    let deployer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    // TokenContract cannot start with an empty supply, so the deployer burns
+    // the initial supply before handing the admin role to the bridge. The
+    // destination supply then only ever reflects what is locked on the source.
+    let dest_token = TokenContractClient::new(&env, &env.register(TokenContract, ()));
+    dest_token.initialize(
+        &deployer,
+        &String::from_str(&env, "Bridged Token"),
+        &String::from_str(&env, "BRG"),
+        &1,
+    );
+    dest_token.burn(&deployer, &1);
+    let dest_bridge = BridgeContractClient::new(&env, &env.register(BridgeContract, ()));
+    dest_token.set_admin(&dest_bridge.address);
+    dest_bridge.initialize(&deployer, &dest_token.address, &BridgeMode::Mint, &s.source_bridge.get_relayers(), &2);
+    assert_eq!(dest_token.get_total_supply(), 0);
+
+    // Source -> destination: locked on the source, minted on the destination
+    let out_nonce = s.source_bridge.bridge_out(&s.user, &recipient, &40_000);
+    let message = dest_bridge.bridge_message(&out_nonce, &recipient, &40_000);
+    let signatures = vec![
+        &env,
+        sign(&env, &s.relayer_keys[0], &message),
+        sign(&env, &s.relayer_keys[1], &message),
+    ];
+    dest_bridge.bridge_in(&out_nonce, &recipient, &40_000, &signatures);
+    assert_eq!(dest_token.balance(&recipient), 40_000);
+
+    // Destination -> source: burned on the destination, released from escrow
+    let back_nonce = dest_bridge.bridge_out(&recipient, &s.user, &15_000);
+    let message = s.source_bridge.bridge_message(&back_nonce, &s.user, &15_000);
+    let signatures = vec![
+        &env,
+        sign(&env, &s.relayer_keys[1], &message),
+        sign(&env, &s.relayer_keys[2], &message),
+    ];
+    s.source_bridge.bridge_in(&back_nonce, &s.user, &15_000, &signatures);
+
+    // Destination supply matches the source escrow exactly
+    assert_eq!(dest_token.balance(&recipient), 25_000);
+    assert_eq!(dest_token.get_total_supply(), 25_000);
+    assert_eq!(s.source_token.balance(&s.source_bridge.address), 25_000);
+    assert_eq!(s.source_token.balance(&s.user), 975_000);
+    assert!(dest_bridge.is_processed(&out_nonce));
+    assert!(s.source_bridge.is_processed(&back_nonce));
+}
+
+#[test]
+#[should_panic(expected = "Relayer duplikat")]
+fn test_set_relayers_rejects_duplicates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_bridge(&env);
+    let key = BytesN::from_array(&env, &s.relayer_keys[0].verifying_key().to_bytes());
+
+    // Should panic: one key would count twice towards the threshold
+    s.source_bridge.set_relayers(&vec![&env, key.clone(), key], &2);
+}
+
+#[test]
+#[should_panic(expected = "Nonce sudah diproses")]
+fn test_bridge_in_replay() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_bridge(&env);
+    let recipient = Address::generate(&env);
+    let nonce = s.source_bridge.bridge_out(&s.user, &recipient, &40_000);
+
+    let message = s.dest_bridge.bridge_message(&nonce, &recipient, &40_000);
+    let signatures = vec![
+        &env,
+        sign(&env, &s.relayer_keys[0], &message),
+        sign(&env, &s.relayer_keys[1], &message),
+    ];
+    s.dest_bridge.bridge_in(&nonce, &recipient, &40_000, &signatures);
+
+    // Should panic
+    s.dest_bridge.bridge_in(&nonce, &recipient, &40_000, &signatures);
+}
+
+#[test]
+#[should_panic(expected = "Quorum relayer belum tercapai")]
+fn test_bridge_in_without_quorum() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_bridge(&env);
+    let recipient = Address::generate(&env);
+    let nonce = s.source_bridge.bridge_out(&s.user, &recipient, &40_000);
+
+    let message = s.dest_bridge.bridge_message(&nonce, &recipient, &40_000);
+    let signatures = vec![&env, sign(&env, &s.relayer_keys[0], &message)];
+
+    // Should panic
+    s.dest_bridge.bridge_in(&nonce, &recipient, &40_000, &signatures);
+}
+
+#[test]
+#[should_panic(expected = "Signature relayer duplikat")]
+fn test_bridge_in_duplicate_signer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_bridge(&env);
+    let recipient = Address::generate(&env);
+    let nonce = s.source_bridge.bridge_out(&s.user, &recipient, &40_000);
+
+    let message = s.dest_bridge.bridge_message(&nonce, &recipient, &40_000);
+    let signature = sign(&env, &s.relayer_keys[0], &message);
+
+    // Should panic
+    s.dest_bridge.bridge_in(&nonce, &recipient, &40_000, &vec![&env, signature.clone(), signature]);
+}
+
+#[test]
+fn test_bridge_in_rejects_tampered_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_bridge(&env);
+    let recipient = Address::generate(&env);
+    let nonce = s.source_bridge.bridge_out(&s.user, &recipient, &40_000);
+
+    // Signatures are for 40_000, the claim is for more
+    let message = s.dest_bridge.bridge_message(&nonce, &recipient, &40_000);
+    let signatures = vec![
+        &env,
+        sign(&env, &s.relayer_keys[0], &message),
+        sign(&env, &s.relayer_keys[1], &message),
+    ];
+    let result = s.dest_bridge.try_bridge_in(&nonce, &recipient, &400_000, &signatures);
+    assert!(result.is_err());
+    assert_eq!(s.dest_token.balance(&recipient), 0);
+    assert!(!s.dest_bridge.is_processed(&nonce));
+}
+
+#[test]
+#[should_panic(expected = "Relayer tidak terdaftar")]
+fn test_bridge_in_unknown_relayer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_bridge(&env);
+    let recipient = Address::generate(&env);
+    let nonce = s.source_bridge.bridge_out(&s.user, &recipient, &40_000);
+
+    let message = s.dest_bridge.bridge_message(&nonce, &recipient, &40_000);
+    let outsider = SigningKey::from_bytes(&[9u8; 32]);
+    let signatures = vec![
+        &env,
+        sign(&env, &s.relayer_keys[0], &message),
+        sign(&env, &outsider, &message),
+    ];
+
+    // Should panic
+    s.dest_bridge.bridge_in(&nonce, &recipient, &40_000, &signatures);
+}
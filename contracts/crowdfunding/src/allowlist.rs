@@ -0,0 +1,112 @@
+use soroban_sdk::{contracterror, contractimpl, panic_with_error, xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+use crate::{CrowdfundingContract, CrowdfundingContractArgs, CrowdfundingContractClient, DataKey};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AllowlistError {
+    NotAllowlisted = 1,
+    InvalidProof = 2,
+}
+
+#[contractimpl]
+impl CrowdfundingContract {
+
+    // Owner lets `donors` into the campaign; the campaign becomes invite-only
+    pub fn add_allowed_donors(env: Env, campaign_id: u32, owner: Address, donors: Vec<Address>) {
+        owner.require_auth();
+
+        let campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_owner(&campaign, &owner);
+
+        // An empty list would lock every donor out
+        if donors.is_empty() {
+            panic!("No donors to allow");
+        }
+
+        for donor in donors.iter() {
+            Self::write_entry(&env, &DataKey::Allowed(campaign_id, donor), &true);
+        }
+
+        Self::make_private(&env, campaign_id);
+    }
+
+    pub fn remove_allowed_donor(env: Env, campaign_id: u32, owner: Address, donor: Address) {
+        owner.require_auth();
+
+        let campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_owner(&campaign, &owner);
+
+        env.storage().persistent().remove(&DataKey::Allowed(campaign_id, donor));
+    }
+
+    // Owner commits to a Merkle tree of allowed donors instead of listing
+    // them. Leaves are sha256 of the donor's XDR-encoded address and pairs
+    // are hashed in sorted order.
+    pub fn set_allowlist_root(env: Env, campaign_id: u32, owner: Address, root: BytesN<32>) {
+        owner.require_auth();
+
+        let campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_owner(&campaign, &owner);
+
+        Self::write_entry(&env, &DataKey::AllowlistRoot(campaign_id), &root);
+
+        Self::make_private(&env, campaign_id);
+    }
+
+    // Donate with a Merkle proof of being on the allowlist. A valid proof
+    // lists the donor, so later donations need no proof.
+    pub fn donate_with_proof(env: Env, campaign_id: u32, donor: Address, amount: i128, proof: Vec<BytesN<32>>) {
+        donor.require_auth();
+
+        let root = Self::get_allowlist_root(env.clone(), campaign_id)
+            .unwrap_or_else(|| panic_with_error!(&env, AllowlistError::NotAllowlisted));
+
+        let leaf: BytesN<32> = env.crypto().sha256(&donor.clone().to_xdr(&env)).into();
+        if Self::merkle_root(&env, leaf, &proof) != root {
+            panic_with_error!(&env, AllowlistError::InvalidProof);
+        }
+
+        Self::write_entry(&env, &DataKey::Allowed(campaign_id, donor.clone()), &true);
+
+        Self::accept_donation(&env, campaign_id, &donor, amount);
+    }
+
+    // Whether only allowlisted donors can give to the campaign
+    pub fn is_private(env: Env, campaign_id: u32) -> bool {
+        env.storage().persistent().get(&DataKey::Private(campaign_id)).unwrap_or(false)
+    }
+
+    // Whether `donor` may give to the campaign right now. Donors only covered
+    // by the Merkle root are listed once they donate with a proof.
+    pub fn is_allowed(env: Env, campaign_id: u32, donor: Address) -> bool {
+        !Self::is_private(env.clone(), campaign_id)
+            || env.storage().persistent().has(&DataKey::Allowed(campaign_id, donor))
+    }
+
+    pub fn get_allowlist_root(env: Env, campaign_id: u32) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::AllowlistRoot(campaign_id))
+    }
+
+    pub(crate) fn require_allowed(env: &Env, campaign_id: u32, donor: &Address) {
+        if !Self::is_allowed(env.clone(), campaign_id, donor.clone()) {
+            panic_with_error!(env, AllowlistError::NotAllowlisted);
+        }
+    }
+
+    fn merkle_root(env: &Env, leaf: BytesN<32>, proof: &Vec<BytesN<32>>) -> BytesN<32> {
+        let mut hash = leaf;
+        for sibling in proof.iter() {
+            let (first, second) = if hash < sibling { (hash, sibling) } else { (sibling, hash) };
+            let mut pair = Bytes::from(first);
+            pair.append(&Bytes::from(second));
+            hash = env.crypto().sha256(&pair).into();
+        }
+        hash
+    }
+
+    fn make_private(env: &Env, campaign_id: u32) {
+        Self::write_entry(env, &DataKey::Private(campaign_id), &true);
+    }
+}
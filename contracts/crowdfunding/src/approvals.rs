@@ -0,0 +1,102 @@
+use soroban_sdk::{contractevent, contractimpl, contracttype, Address, Env};
+
+use crate::{CrowdfundingContract, CrowdfundingContractArgs, CrowdfundingContractClient, DataKey};
+
+// Donor votes on one milestone, weighted by donation
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MilestoneTally {
+    pub approvals: i128,
+    pub rejections: i128,
+}
+
+// Donors holding a majority of the donated funds rejected the next milestone,
+// so the campaign was abandoned
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneRejected {
+    #[topic]
+    pub campaign_id: u32,
+    pub milestone: u32,
+}
+
+#[contractimpl]
+impl CrowdfundingContract {
+
+    // Donor votes on the next milestone of a successful campaign. Every
+    // milestone after the first needs more than half of the donated funds to
+    // approve before it can be released; if more than half reject it, the
+    // campaign is abandoned and donors can refund what is left. Voting opens
+    // once the first milestone is paid; sponsor matches do not count.
+    pub fn vote_milestone(env: Env, campaign_id: u32, donor: Address, approve: bool) {
+        donor.require_auth();
+
+        let mut campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_not_cancelled(&campaign);
+
+        if env.ledger().timestamp() <= campaign.deadline || campaign.raised < campaign.goal {
+            panic!("Only a successful campaign has milestones to vote on");
+        }
+        if campaign.abandoned {
+            panic!("Campaign has been abandoned");
+        }
+
+        let milestone = campaign.milestones_released;
+        if milestone >= campaign.milestones.len() {
+            panic!("No milestone left to vote on");
+        }
+        if milestone == 0 {
+            panic!("The first milestone needs no approval");
+        }
+
+        let weight = Self::get_donation(env.clone(), campaign_id, donor.clone());
+        if weight <= 0 {
+            panic!("Only donors can vote");
+        }
+
+        let vote_key = DataKey::MilestoneVote(campaign_id, milestone, donor);
+        if env.storage().persistent().has(&vote_key) {
+            panic!("Already voted");
+        }
+        Self::write_entry(&env, &vote_key, &approve);
+
+        let mut tally = Self::get_milestone_tally(env.clone(), campaign_id, milestone);
+        if approve {
+            tally.approvals += weight;
+        } else {
+            tally.rejections += weight;
+        }
+
+        Self::write_entry(&env, &DataKey::MilestoneTally(campaign_id, milestone), &tally);
+
+        if tally.rejections * 2 > Self::donor_raised(&env, campaign_id, &campaign) {
+            campaign.abandoned = true;
+            Self::write_campaign(&env, campaign_id, &campaign);
+
+            MilestoneRejected { campaign_id, milestone }.publish(&env);
+        }
+    }
+
+    pub fn get_milestone_tally(env: Env, campaign_id: u32, milestone: u32) -> MilestoneTally {
+        env.storage().persistent()
+            .get(&DataKey::MilestoneTally(campaign_id, milestone))
+            .unwrap_or_default()
+    }
+
+    // None if the donor has not voted on the milestone
+    pub fn get_milestone_vote(env: Env, campaign_id: u32, milestone: u32, donor: Address) -> Option<bool> {
+        env.storage().persistent().get(&DataKey::MilestoneVote(campaign_id, milestone, donor))
+    }
+
+    // The first milestone is paid once the campaign succeeds; later ones
+    // wait for donor approval
+    pub fn is_milestone_approved(env: Env, campaign_id: u32, milestone: u32) -> bool {
+        if milestone == 0 {
+            return true;
+        }
+
+        let donated = Self::get_campaign(env.clone(), campaign_id)
+            .map_or(0, |campaign| Self::donor_raised(&env, campaign_id, &campaign));
+        Self::get_milestone_tally(env, campaign_id, milestone).approvals * 2 > donated
+    }
+}
@@ -0,0 +1,170 @@
+use soroban_sdk::{contractclient, contractimpl, contracttype, token, Address, Env, Vec};
+
+use crate::{Campaign, CrowdfundingContract, CrowdfundingContractArgs, CrowdfundingContractClient, DataKey};
+
+// Conversion rates are campaign token units per token unit, scaled by this
+pub const RATE_PRECISION: i128 = 10_000_000;
+
+// How an extra token is valued against the campaign token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Pricing {
+    Fixed(i128),
+    Oracle(Address),
+}
+
+// Any contract quoting `asset` in `base` units, scaled by RATE_PRECISION
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn price(env: Env, asset: Address, base: Address) -> i128;
+}
+
+#[contractimpl]
+impl CrowdfundingContract {
+
+    // Owner whitelists another token for a running campaign, or changes how
+    // an already whitelisted one is priced as long as nobody has donated it
+    pub fn add_accepted_token(env: Env, campaign_id: u32, owner: Address, token: Address, pricing: Pricing) {
+        owner.require_auth();
+
+        let campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_owner(&campaign, &owner);
+        Self::require_open(&env, &campaign);
+
+        if token == campaign.token {
+            panic!("Campaign token is always accepted");
+        }
+        if Self::get_token_total(env.clone(), campaign_id, token.clone()) > 0 {
+            panic!("Token already has donations");
+        }
+        if let Pricing::Fixed(rate) = pricing {
+            if rate <= 0 {
+                panic!("Conversion rate must be positive");
+            }
+        }
+
+        let mut accepted = Self::read_accepted_tokens(&env, campaign_id);
+        if !accepted.contains(&token) {
+            accepted.push_back(token.clone());
+            Self::write_entry(&env, &DataKey::AcceptedTokens(campaign_id), &accepted);
+        }
+        Self::write_entry(&env, &DataKey::TokenPricing(campaign_id, token), &pricing);
+    }
+
+    // Donate any whitelisted token. The donation counts towards the goal at
+    // its value in campaign token units when it is made.
+    pub fn donate_token(env: Env, campaign_id: u32, donor: Address, token: Address, amount: i128) {
+        donor.require_auth();
+
+        let campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_open(&env, &campaign);
+
+        if amount <= 0 {
+            panic!("Donation amount must be positive");
+        }
+
+        let mut amount = amount;
+        let mut value = Self::token_value(&env, campaign_id, &campaign, &token, amount);
+        if value <= 0 {
+            panic!("Donation too small to count towards the goal");
+        }
+
+        // Scale the donation down to what fits under the hard cap
+        let accepted = Self::apply_limits(&env, campaign_id, &campaign, &donor, value);
+        if accepted < value {
+            let scaled = amount * accepted / value;
+            value = value * scaled / amount;
+            amount = scaled;
+            if value <= 0 {
+                panic!("Donation too small to count towards the goal");
+            }
+        }
+
+        let token_client = token::Client::new(&env, &token);
+        let contract_address = env.current_contract_address();
+        token_client.transfer(&donor, &contract_address, &amount);
+
+        Self::record_donation(&env, campaign_id, campaign, donor, token, amount, value);
+    }
+
+    // The campaign token followed by every whitelisted token
+    pub fn get_accepted_tokens(env: Env, campaign_id: u32) -> Vec<Address> {
+        match Self::get_campaign(env.clone(), campaign_id) {
+            Some(campaign) => Self::campaign_tokens(&env, campaign_id, &campaign),
+            None => Vec::new(&env),
+        }
+    }
+
+    // None for the campaign token and for tokens that are not whitelisted
+    pub fn get_token_pricing(env: Env, campaign_id: u32, token: Address) -> Option<Pricing> {
+        env.storage().persistent().get(&DataKey::TokenPricing(campaign_id, token))
+    }
+
+    // Value of `amount` of `token` in campaign token units at the current rate
+    pub fn quote_donation(env: Env, campaign_id: u32, token: Address, amount: i128) -> i128 {
+        let campaign = Self::read_campaign(&env, campaign_id);
+        Self::token_value(&env, campaign_id, &campaign, &token, amount)
+    }
+
+    // Amount of `token` the donor has in the campaign
+    pub fn get_token_donation(env: Env, campaign_id: u32, donor: Address, token: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::TokenDonation(campaign_id, donor, token)).unwrap_or(0)
+    }
+
+    // Amount of `token` the campaign holds for its donors
+    pub fn get_token_total(env: Env, campaign_id: u32, token: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::TokenTotal(campaign_id, token)).unwrap_or(0)
+    }
+
+    pub(crate) fn campaign_tokens(env: &Env, campaign_id: u32, campaign: &Campaign) -> Vec<Address> {
+        let mut tokens = Vec::new(env);
+        tokens.push_back(campaign.token.clone());
+        tokens.append(&Self::read_accepted_tokens(env, campaign_id));
+        tokens
+    }
+
+    pub(crate) fn record_token_donation(env: &Env, campaign_id: u32, donor: &Address, token: &Address, amount: i128) {
+        let donated = Self::get_token_donation(env.clone(), campaign_id, donor.clone(), token.clone());
+        let key = DataKey::TokenDonation(campaign_id, donor.clone(), token.clone());
+        Self::write_entry(env, &key, &(donated + amount));
+
+        Self::adjust_token_total(env, campaign_id, token, amount);
+    }
+
+    // Clear the donor's balance of `token` and return what it was
+    pub(crate) fn take_token_donation(env: &Env, campaign_id: u32, donor: &Address, token: &Address) -> i128 {
+        let amount = Self::get_token_donation(env.clone(), campaign_id, donor.clone(), token.clone());
+        if amount != 0 {
+            env.storage().persistent().remove(&DataKey::TokenDonation(campaign_id, donor.clone(), token.clone()));
+        }
+        amount
+    }
+
+    pub(crate) fn adjust_token_total(env: &Env, campaign_id: u32, token: &Address, delta: i128) {
+        let total = Self::get_token_total(env.clone(), campaign_id, token.clone());
+        Self::write_entry(env, &DataKey::TokenTotal(campaign_id, token.clone()), &(total + delta));
+    }
+
+    fn token_value(env: &Env, campaign_id: u32, campaign: &Campaign, token: &Address, amount: i128) -> i128 {
+        if *token == campaign.token {
+            return amount;
+        }
+
+        let pricing = Self::get_token_pricing(env.clone(), campaign_id, token.clone())
+            .unwrap_or_else(|| panic!("Token not accepted"));
+        let rate = match pricing {
+            Pricing::Fixed(rate) => rate,
+            Pricing::Oracle(oracle) => PriceOracleClient::new(env, &oracle).price(token, &campaign.token),
+        };
+        if rate <= 0 {
+            panic!("Invalid token price");
+        }
+
+        amount * rate / RATE_PRECISION
+    }
+
+    // Whitelisted tokens in the order they were added
+    fn read_accepted_tokens(env: &Env, campaign_id: u32) -> Vec<Address> {
+        env.storage().persistent().get(&DataKey::AcceptedTokens(campaign_id)).unwrap_or(Vec::new(env))
+    }
+}
@@ -0,0 +1,110 @@
+use soroban_sdk::{contractevent, contractimpl, contracttype, Address, Env};
+
+use crate::{Campaign, CrowdfundingContract, CrowdfundingContractArgs, CrowdfundingContractClient, DataKey};
+
+// Owner's request to move the deadline, with votes weighted by donation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Extension {
+    pub new_deadline: u64,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub applied: bool,
+}
+
+// Donors holding a majority of the donated funds approved the extension
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtensionApplied {
+    #[topic]
+    pub campaign_id: u32,
+    pub old_deadline: u64,
+    pub new_deadline: u64,
+}
+
+#[contractimpl]
+impl CrowdfundingContract {
+
+    // Owner asks donors for a later deadline. Each campaign gets one proposal.
+    pub fn propose_extension(env: Env, campaign_id: u32, owner: Address, new_deadline: u64) {
+        owner.require_auth();
+
+        let campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_owner(&campaign, &owner);
+        Self::require_open(&env, &campaign);
+
+        if new_deadline <= campaign.deadline {
+            panic!("New deadline must be after the current one");
+        }
+
+        if Self::get_extension(env.clone(), campaign_id).is_some() {
+            panic!("Extension already proposed");
+        }
+
+        let extension = Extension { new_deadline, votes_for: 0, votes_against: 0, applied: false };
+        Self::write_entry(&env, &DataKey::Extension(campaign_id), &extension);
+    }
+
+    // Donor votes with the weight of their donation. The extension applies as
+    // soon as more than half of the donated funds approve, which has to happen
+    // before the original deadline. Sponsor matches do not count.
+    pub fn vote_extension(env: Env, campaign_id: u32, donor: Address, approve: bool) {
+        donor.require_auth();
+
+        let mut campaign = Self::read_campaign(&env, campaign_id);
+        let mut extension = Self::get_extension(env.clone(), campaign_id)
+            .unwrap_or_else(|| panic!("No extension proposed"));
+
+        if extension.applied {
+            panic!("Extension already applied");
+        }
+        Self::require_open(&env, &campaign);
+
+        let weight = Self::get_donation(env.clone(), campaign_id, donor.clone());
+        if weight <= 0 {
+            panic!("Only donors can vote");
+        }
+
+        let vote_key = DataKey::ExtensionVote(campaign_id, donor);
+        if env.storage().persistent().has(&vote_key) {
+            panic!("Already voted");
+        }
+        Self::write_entry(&env, &vote_key, &approve);
+
+        if approve {
+            extension.votes_for += weight;
+        } else {
+            extension.votes_against += weight;
+        }
+
+        if extension.votes_for * 2 > Self::donor_raised(&env, campaign_id, &campaign) {
+            extension.applied = true;
+
+            let old_deadline = campaign.deadline;
+            campaign.deadline = extension.new_deadline;
+            Self::write_campaign(&env, campaign_id, &campaign);
+
+            ExtensionApplied { campaign_id, old_deadline, new_deadline: extension.new_deadline }.publish(&env);
+        }
+
+        Self::write_entry(&env, &DataKey::Extension(campaign_id), &extension);
+    }
+
+    pub fn get_extension(env: Env, campaign_id: u32) -> Option<Extension> {
+        env.storage().persistent().get(&DataKey::Extension(campaign_id))
+    }
+
+    // None if the donor has not voted on the campaign's extension
+    pub fn get_extension_vote(env: Env, campaign_id: u32, donor: Address) -> Option<bool> {
+        env.storage().persistent().get(&DataKey::ExtensionVote(campaign_id, donor))
+    }
+
+    // Donors outvoted on an applied extension may leave while the campaign runs
+    pub(crate) fn is_extension_dissenter(env: &Env, campaign_id: u32, campaign: &Campaign, donor: &Address) -> bool {
+        let applied = Self::get_extension(env.clone(), campaign_id).is_some_and(|extension| extension.applied);
+
+        applied
+            && env.ledger().timestamp() <= campaign.deadline
+            && Self::get_extension_vote(env.clone(), campaign_id, donor.clone()) == Some(false)
+    }
+}
@@ -0,0 +1,78 @@
+use soroban_sdk::{contractimpl, contracttype, token, Address, Env, Vec};
+
+use crate::{Campaign, CrowdfundingContract, CrowdfundingContractArgs, CrowdfundingContractClient, Milestone, TOTAL_SHARE_BPS};
+
+// Upper bound on the platform's cut, in basis points
+pub const MAX_PLATFORM_FEE_BPS: u32 = 1_000;
+
+// Cut of every successful payout that goes to the platform running the campaign
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFee {
+    pub treasury: Address,
+    pub fee_bps: u32,
+}
+
+#[contractimpl]
+impl CrowdfundingContract {
+
+    // Same as create_campaign, with a platform fee taken from the owner's
+    // payouts. Refunds are never charged.
+    pub fn create_campaign_with_fee(
+        env: Env,
+        owner: Address,
+        goal: i128,
+        deadline: u64,
+        token: Address,
+        milestones: Vec<Milestone>,
+        platform_fee: PlatformFee,
+    ) -> u32 {
+        if platform_fee.fee_bps > MAX_PLATFORM_FEE_BPS {
+            panic!("Platform fee too high");
+        }
+
+        Self::register_campaign(&env, owner, goal, deadline, token, milestones, Some(platform_fee))
+    }
+
+    pub fn get_platform_fee_config(env: Env, campaign_id: u32) -> Option<PlatformFee> {
+        let campaign = Self::get_campaign(env, campaign_id)?;
+        Some(PlatformFee { treasury: campaign.treasury?, fee_bps: campaign.fee_bps })
+    }
+
+    // Everything raised, before the platform fee
+    pub fn get_gross_raised(env: Env, campaign_id: u32) -> i128 {
+        Self::get_total_raised(env, campaign_id)
+    }
+
+    // Platform's cut of the raised funds
+    pub fn get_platform_fee(env: Env, campaign_id: u32) -> i128 {
+        Self::get_campaign(env, campaign_id)
+            .map_or(0, |campaign| Self::platform_fee_on(&campaign, campaign.raised))
+    }
+
+    // What the owner receives once the platform fee is taken
+    pub fn get_net_payable(env: Env, campaign_id: u32) -> i128 {
+        Self::get_campaign(env, campaign_id)
+            .map_or(0, |campaign| campaign.raised - Self::platform_fee_on(&campaign, campaign.raised))
+    }
+
+    pub(crate) fn platform_fee_on(campaign: &Campaign, amount: i128) -> i128 {
+        amount * campaign.fee_bps as i128 / TOTAL_SHARE_BPS as i128
+    }
+
+    // Send `amount` of `token` to the owner, less the platform's cut
+    pub(crate) fn pay_owner(env: &Env, campaign: &Campaign, token: &Address, amount: i128) {
+        let token_client = token::Client::new(env, token);
+        let contract_address = env.current_contract_address();
+
+        let fee = Self::platform_fee_on(campaign, amount);
+        if fee > 0 {
+            // A fee is only ever configured together with a treasury
+            let treasury = campaign.treasury.as_ref().unwrap();
+            token_client.transfer(&contract_address, treasury, &fee);
+        }
+        if amount - fee > 0 {
+            token_client.transfer(&contract_address, &campaign.owner, &(amount - fee));
+        }
+    }
+}
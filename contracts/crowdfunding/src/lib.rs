@@ -1,13 +1,124 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env, Map, Symbol};
+use soroban_sdk::{
+    contract, contractevent, contractimpl, contracttype, token, xdr::FromXdr, Address, Bytes,
+    BytesN, Env, IntoVal, Val, Vec,
+};
+
+mod allowlist;
+mod approvals;
+mod assets;
+mod extension;
+mod fees;
+mod limits;
+mod matching;
+mod receipts;
+mod tiers;
+
+pub use allowlist::AllowlistError;
+pub use approvals::{MilestoneRejected, MilestoneTally};
+pub use assets::{Pricing, PriceOracle, PriceOracleClient, RATE_PRECISION};
+pub use extension::{Extension, ExtensionApplied};
+pub use fees::{PlatformFee, MAX_PLATFORM_FEE_BPS};
+pub use limits::DonationLimits;
+pub use matching::MatchPool;
+pub use receipts::{DonorReceipts, DonorReceiptsClient, Receipt};
+pub use tiers::RewardTier;
+
+// Entries are extended to ~30 days whenever they are written and less than
+// ~29 days are left
+pub(crate) const ENTRY_TTL_THRESHOLD: u32 = 500_000;
+pub(crate) const ENTRY_TTL_EXTEND: u32 = 518_400;
+
+// Storage keys. Everything but the campaign counter is a persistent entry per
+// campaign or per (campaign, donor), so no call loads state it does not touch.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    CampaignCount,
+    Campaign(u32),
+    Donation(u32, Address),
+    OwnerCampaigns(Address),
+    AcceptedTokens(u32),
+    TokenPricing(u32, Address),
+    TokenDonation(u32, Address, Address),
+    TokenTotal(u32, Address),
+    MilestoneTally(u32, u32),
+    MilestoneVote(u32, u32, Address),
+    RewardTiers(u32),
+    DonorTier(u32, Address),
+    TierHolders(u32, u32),
+    Receipt(u32, Address),
+    BackedCampaigns(Address),
+    Extension(u32),
+    ExtensionVote(u32, Address),
+    MatchPool(u32),
+    Matched(u32, Address),
+    DonationLimits(u32),
+    Private(u32),
+    Allowed(u32, Address),
+    AllowlistRoot(u32),
+}
+
+// Milestone shares are in basis points of the total raised
+pub const TOTAL_SHARE_BPS: u32 = 10_000;
+
+// One tranche of the raised funds, released to the owner in order
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub description_hash: BytesN<32>,
+    pub share_bps: u32,
+}
+
+// Everything the registry keeps about a single campaign
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Campaign {
+    pub owner: Address,
+    pub goal: i128,
+    pub deadline: u64,
+    pub token: Address,
+    pub raised: i128,
+    pub milestones: Vec<Milestone>,
+    pub milestones_released: u32,
+    pub released: i128,
+    pub abandoned: bool,
+    pub withdrawn: i128,
+    pub cancel_reason: Option<BytesN<32>>,
+    pub treasury: Option<Address>,
+    pub fee_bps: u32,
+}
+
+// Percentages of the soft goal and of the hard cap raised so far
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Progress {
+    pub goal_percentage: i128,
+    pub cap_percentage: Option<i128>,
+}
+
+// Owner took the funds of a successful campaign without milestones;
+// `amount` is what the owner received after the platform fee
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Withdrawn {
+    #[topic]
+    pub campaign_id: u32,
+    #[topic]
+    pub owner: Address,
+    pub amount: i128,
+}
 
-const CAMPAIGN_GOAL: Symbol = symbol_short!("goal");
-const CAMPAIGN_DEADLINE: Symbol = symbol_short!("deadline");
-const TOTAL_RAISED: Symbol = symbol_short!("raised");
-const DONATIONS: Symbol = symbol_short!("donations");
-const CAMPAIGN_OWNER: Symbol = symbol_short!("owner");
-const XLM_TOKEN_ADDRESS: Symbol = symbol_short!("xlm_addr");
-const IS_ALREADY_INIT: Symbol = symbol_short!("is_init");
+// Owner called the campaign off; donors can refund right away
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cancelled {
+    #[topic]
+    pub campaign_id: u32,
+    #[topic]
+    pub owner: Address,
+    pub reason_hash: BytesN<32>,
+}
 
 #[contract]
 pub struct CrowdfundingContract;
@@ -15,161 +126,514 @@ pub struct CrowdfundingContract;
 #[contractimpl]
 impl CrowdfundingContract {
 
-    pub fn initialize(
+    // Register a new campaign and return its id
+    pub fn create_campaign(
         env: Env,
         owner: Address,
         goal: i128,
         deadline: u64,
-        xlm_token: Address,
-    ) {
-        owner.require_auth();
-
-        // Check if already initialized
-        let is_init: bool = env.storage().instance().get(&IS_ALREADY_INIT).unwrap_or(false);
-        if is_init {
-            panic!("Contract already initialized");
-        }
+        token: Address,
+        milestones: Vec<Milestone>,
+    ) -> u32 {
+        Self::register_campaign(&env, owner, goal, deadline, token, milestones, None)
+    }
 
-        env.storage().instance().set(&CAMPAIGN_OWNER, &owner);
-        env.storage().instance().set(&CAMPAIGN_GOAL, &goal);
-        env.storage().instance().set(&CAMPAIGN_DEADLINE, &deadline);
-        env.storage().instance().set(&TOTAL_RAISED, &0i128);
-        env.storage().instance().set(&XLM_TOKEN_ADDRESS, &xlm_token);
-        env.storage().instance().set(&IS_ALREADY_INIT, &true);
+    pub fn donate(env: Env, campaign_id: u32, donor: Address, amount: i128) {
+        donor.require_auth();
 
-        let donations: Map<Address, i128> = Map::new(&env);
-        env.storage().instance().set(&DONATIONS, &donations);
+        Self::accept_donation(&env, campaign_id, &donor, amount);
     }
 
-    pub fn donate(env: Env, donor: Address, amount: i128) {
-        donor.require_auth();
+    // Callback from the campaign token's transfer_and_call: the tokens are
+    // already in this contract, so only the bookkeeping is left. `data` is
+    // the XDR-encoded campaign id.
+    pub fn on_token_received(env: Env, from: Address, amount: i128, data: Bytes) {
+        let campaign_id = u32::from_xdr(&env, &data)
+            .unwrap_or_else(|_| panic!("Invalid campaign id"));
+        let campaign = Self::read_campaign(&env, campaign_id);
 
-        // Check initialization
-        Self::require_initialized(&env);
+        // Only the campaign token itself may report incoming tokens
+        campaign.token.require_auth();
 
-        let deadline: u64 = env.storage().instance().get(&CAMPAIGN_DEADLINE).unwrap();
-        if env.ledger().timestamp() > deadline {
-            panic!("Campaign has ended");
-        }
+        Self::require_open(&env, &campaign);
 
         if amount <= 0 {
             panic!("Donation amount must be positive");
         }
 
-        let xlm_token_address: Address = env.storage().instance().get(&XLM_TOKEN_ADDRESS).unwrap();
-        let xlm_token = token::Client::new(&env, &xlm_token_address);
-        let contract_address = env.current_contract_address();
+        // The tokens already arrived, so there is no partial acceptance here
+        if Self::apply_limits(&env, campaign_id, &campaign, &from, amount) < amount {
+            panic!("Donation exceeds hard cap");
+        }
 
-        xlm_token.transfer(&donor, &contract_address, &amount);
+        let token = campaign.token.clone();
+        Self::record_donation(&env, campaign_id, campaign, from, token, amount, amount);
+    }
+
+    pub fn get_campaign(env: Env, campaign_id: u32) -> Option<Campaign> {
+        env.storage().persistent().get(&DataKey::Campaign(campaign_id))
+    }
+
+    pub fn get_campaign_count(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::CampaignCount).unwrap_or(0)
+    }
 
-        let mut total: i128 = env.storage().instance().get(&TOTAL_RAISED).unwrap();
-        total += amount;
-        env.storage().instance().set(&TOTAL_RAISED, &total);
+    // Campaign ids starting at `start`, at most `limit` of them
+    pub fn list_campaigns(env: Env, start: u32, limit: u32) -> Vec<u32> {
+        let count = Self::get_campaign_count(env.clone());
+        let end = start.saturating_add(limit).min(count);
 
-        let mut donations: Map<Address, i128> = env.storage().instance().get(&DONATIONS).unwrap();
-        let current_donation = donations.get(donor.clone()).unwrap_or(0);
-        donations.set(donor, current_donation + amount);
-        env.storage().instance().set(&DONATIONS, &donations);
+        let mut result = Vec::new(&env);
+        for campaign_id in start..end {
+            result.push_back(campaign_id);
+        }
+        result
     }
 
-    pub fn get_total_raised(env: Env) -> i128 {
-        env.storage().instance().get(&TOTAL_RAISED).unwrap_or(0)
+    pub fn get_campaigns_by_owner(env: Env, owner: Address) -> Vec<u32> {
+        env.storage().persistent()
+            .get(&DataKey::OwnerCampaigns(owner))
+            .unwrap_or(Vec::new(&env))
     }
 
-    pub fn get_donation(env: Env, donor: Address) -> i128 {
-        let donations: Map<Address, i128> = env.storage().instance()
-            .get(&DONATIONS)
-            .unwrap_or(Map::new(&env));
-        donations.get(donor).unwrap_or(0)
+    pub fn get_total_raised(env: Env, campaign_id: u32) -> i128 {
+        Self::get_campaign(env, campaign_id).map_or(0, |campaign| campaign.raised)
     }
 
-    pub fn get_is_already_init(env: Env) -> bool {
-        env.storage().instance().get(&IS_ALREADY_INIT).unwrap_or(false)
+    // Value of everything the donor gave, in campaign token units
+    pub fn get_donation(env: Env, campaign_id: u32, donor: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::Donation(campaign_id, donor)).unwrap_or(0)
     }
 
-    // ✅ FIXED: Return default 0 if not initialized
-    pub fn get_goal(env: Env) -> i128 {
-        env.storage().instance().get(&CAMPAIGN_GOAL).unwrap_or(0)
+    // Returns 0 for a campaign that does not exist
+    pub fn get_goal(env: Env, campaign_id: u32) -> i128 {
+        Self::get_campaign(env, campaign_id).map_or(0, |campaign| campaign.goal)
     }
 
-    // ✅ FIXED: Return default 0 if not initialized
-    pub fn get_deadline(env: Env) -> u64 {
-        env.storage().instance().get(&CAMPAIGN_DEADLINE).unwrap_or(0)
+    // Returns 0 for a campaign that does not exist
+    pub fn get_deadline(env: Env, campaign_id: u32) -> u64 {
+        Self::get_campaign(env, campaign_id).map_or(0, |campaign| campaign.deadline)
     }
 
-    pub fn is_goal_reached(env: Env) -> bool {
-        let total_raised: i128 = env.storage().instance().get(&TOTAL_RAISED).unwrap_or(0);
-        let goal: i128 = env.storage().instance().get(&CAMPAIGN_GOAL).unwrap_or(0);
-        if goal == 0 {
-            return false;
+    pub fn is_goal_reached(env: Env, campaign_id: u32) -> bool {
+        match Self::get_campaign(env, campaign_id) {
+            Some(campaign) => campaign.raised >= campaign.goal,
+            None => false,
         }
-        total_raised >= goal
     }
 
-    pub fn is_ended(env: Env) -> bool {
-        let deadline: u64 = env.storage().instance().get(&CAMPAIGN_DEADLINE).unwrap_or(0);
-        if deadline == 0 {
-            return false;
+    pub fn is_ended(env: Env, campaign_id: u32) -> bool {
+        match Self::get_campaign(env.clone(), campaign_id) {
+            Some(campaign) => env.ledger().timestamp() > campaign.deadline,
+            None => false,
         }
-        env.ledger().timestamp() > deadline
     }
 
-    pub fn get_progress_percentage(env: Env) -> i128 {
-        let total_raised: i128 = env.storage().instance().get(&TOTAL_RAISED).unwrap_or(0);
-        let goal: i128 = env.storage().instance().get(&CAMPAIGN_GOAL).unwrap_or(0);
-        if goal == 0 {
-            return 0;
+    // Progress towards the goal, and towards the hard cap when there is one
+    pub fn get_progress_percentage(env: Env, campaign_id: u32) -> Progress {
+        match Self::get_campaign(env.clone(), campaign_id) {
+            Some(campaign) => Progress {
+                goal_percentage: (campaign.raised * 100) / campaign.goal,
+                cap_percentage: Self::get_donation_limits(env, campaign_id)
+                    .hard_cap
+                    .map(|hard_cap| (campaign.raised * 100) / hard_cap),
+            },
+            None => Progress { goal_percentage: 0, cap_percentage: None },
         }
-        (total_raised * 100) / goal
     }
 
-    pub fn refund(env: Env, donor: Address) -> i128 {
+    pub fn refund(env: Env, campaign_id: u32, donor: Address) -> i128 {
         donor.require_auth();
-        
-        Self::require_initialized(&env);
 
-        let deadline: u64 = env.storage().instance().get(&CAMPAIGN_DEADLINE).unwrap();
-        let goal: i128 = env.storage().instance().get(&CAMPAIGN_GOAL).unwrap();
-        let total_raised: i128 = env.storage().instance().get(&TOTAL_RAISED).unwrap_or(0);
+        let mut campaign = Self::read_campaign(&env, campaign_id);
+
+        // A cancelled campaign refunds in full straight away, and so does an
+        // extended one for donors who voted against the extension
+        let immediate = campaign.cancel_reason.is_some()
+            || Self::is_extension_dissenter(&env, campaign_id, &campaign, &donor);
+        if !immediate {
+            if env.ledger().timestamp() <= campaign.deadline {
+                panic!("Campaign belum berakhir");
+            }
+            // An abandoned campaign refunds what is left even if the goal was reached
+            if campaign.raised >= campaign.goal && !campaign.abandoned {
+                panic!("Goal sudah tercapai, tidak bisa refund");
+            }
+        }
+
+        let donated_amount = Self::get_donation(env.clone(), campaign_id, donor.clone());
 
-        if env.ledger().timestamp() <= deadline {
+        if donated_amount <= 0 {
+            panic!("No donations found for this address");
+        }
+
+        // Pro-rata share of the funds that were never released to the owner
+        let unreleased_bps = (TOTAL_SHARE_BPS - Self::released_share_bps(&campaign)) as i128;
+        let pro_rata = |amount: i128| amount * unreleased_bps / TOTAL_SHARE_BPS as i128;
+        let refund_amount = if campaign.abandoned {
+            pro_rata(donated_amount)
+        } else {
+            donated_amount
+        };
+
+        // Every token goes back in kind
+        let contract_address = env.current_contract_address();
+        for token in Self::campaign_tokens(&env, campaign_id, &campaign).iter() {
+            let donated = Self::take_token_donation(&env, campaign_id, &donor, &token);
+            let amount = if campaign.abandoned {
+                pro_rata(donated)
+            } else {
+                Self::adjust_token_total(&env, campaign_id, &token, -donated);
+                donated
+            };
+
+            if amount > 0 {
+                token::Client::new(&env, &token).transfer(&contract_address, &donor, &amount);
+            }
+        }
+
+        // The sponsor's match for this donor goes back to the pool
+        let matched = if campaign.abandoned {
+            Self::reverse_match(&env, campaign_id, &donor, pro_rata)
+        } else {
+            Self::reverse_match(&env, campaign_id, &donor, |amount| amount)
+        };
+
+        // Total raised stays fixed after abandonment so every donor gets the same ratio
+        if !campaign.abandoned {
+            campaign.raised -= donated_amount + matched;
+            Self::adjust_token_total(&env, campaign_id, &campaign.token, -matched);
+            Self::write_campaign(&env, campaign_id, &campaign);
+        }
+
+        env.storage().persistent().remove(&DataKey::Donation(campaign_id, donor.clone()));
+
+        Self::release_tier(&env, campaign_id, &donor);
+        Self::burn_receipt(&env, campaign_id, &donor);
+
+        refund_amount
+    }
+
+    // Owner takes everything raised by a successful campaign, in every token
+    // donated. Campaigns with milestones are paid out through release_milestone
+    // instead. Returns the value paid to the owner after the platform fee, in
+    // campaign token units.
+    pub fn withdraw(env: Env, campaign_id: u32, owner: Address) -> i128 {
+        owner.require_auth();
+
+        let mut campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_owner(&campaign, &owner);
+        Self::require_not_cancelled(&campaign);
+
+        if env.ledger().timestamp() <= campaign.deadline {
             panic!("Campaign belum berakhir");
         }
-        if total_raised >= goal {
-            panic!("Goal sudah tercapai, tidak bisa refund");
+        if campaign.raised < campaign.goal {
+            panic!("Goal not reached");
+        }
+        if !campaign.milestones.is_empty() {
+            panic!("Campaign funds are released per milestone");
+        }
+        if campaign.withdrawn > 0 {
+            panic!("Funds already withdrawn");
         }
 
-        let mut donations: Map<Address, i128> = env.storage().instance().get(&DONATIONS).unwrap();
-        let donated_amount = donations.get(donor.clone()).unwrap_or(0);
+        let amount = campaign.raised;
+        campaign.withdrawn = amount;
+        Self::write_campaign(&env, campaign_id, &campaign);
 
-        if donated_amount <= 0 {
-            panic!("No donations found for this address");
+        for token in Self::campaign_tokens(&env, campaign_id, &campaign).iter() {
+            let total = Self::get_token_total(env.clone(), campaign_id, token.clone());
+            Self::pay_owner(&env, &campaign, &token, total);
+        }
+
+        let amount = amount - Self::platform_fee_on(&campaign, amount);
+        Withdrawn { campaign_id, owner, amount }.publish(&env);
+
+        amount
+    }
+
+    // Gross amount withdrawn including the platform fee, 0 until withdraw succeeds
+    pub fn get_withdrawn(env: Env, campaign_id: u32) -> i128 {
+        Self::get_campaign(env, campaign_id).map_or(0, |campaign| campaign.withdrawn)
+    }
+
+    // Owner releases the next milestone of a successful campaign, paying its
+    // share of every token donated. Milestones after the first need donor
+    // approval (see vote_milestone). Returns the value paid to the owner after
+    // the platform fee, in campaign token units.
+    pub fn release_milestone(env: Env, campaign_id: u32, owner: Address) -> i128 {
+        owner.require_auth();
+
+        let mut campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_owner(&campaign, &owner);
+        Self::require_not_cancelled(&campaign);
+
+        if env.ledger().timestamp() <= campaign.deadline {
+            panic!("Campaign belum berakhir");
+        }
+        if campaign.raised < campaign.goal {
+            panic!("Goal not reached");
+        }
+        if campaign.abandoned {
+            panic!("Campaign has been abandoned");
+        }
+
+        let index = campaign.milestones_released;
+        if campaign.milestones.is_empty() {
+            panic!("Campaign has no milestones");
+        }
+        if index >= campaign.milestones.len() {
+            panic!("All milestones already released");
+        }
+        if !Self::is_milestone_approved(env.clone(), campaign_id, index) {
+            panic!("Milestone not approved by donors");
+        }
+
+        // Paying the difference of the cumulative shares lets the last
+        // milestone take whatever rounding left behind
+        let before = Self::released_share_bps(&campaign) as i128;
+        let after = before + campaign.milestones.get(index).unwrap().share_bps as i128;
+        let tranche = |total: i128| {
+            total * after / TOTAL_SHARE_BPS as i128 - total * before / TOTAL_SHARE_BPS as i128
+        };
+        let amount = tranche(campaign.raised);
+
+        campaign.milestones_released = index + 1;
+        campaign.released += amount;
+        Self::write_campaign(&env, campaign_id, &campaign);
+
+        for token in Self::campaign_tokens(&env, campaign_id, &campaign).iter() {
+            let payout = tranche(Self::get_token_total(env.clone(), campaign_id, token.clone()));
+            Self::pay_owner(&env, &campaign, &token, payout);
+        }
+
+        amount - Self::platform_fee_on(&campaign, amount)
+    }
+
+    // Owner gives up on the remaining milestones; donors can then refund
+    // their share of the unreleased funds. Donors can force the same by
+    // rejecting the next milestone.
+    pub fn abandon(env: Env, campaign_id: u32, owner: Address) {
+        owner.require_auth();
+
+        let mut campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_owner(&campaign, &owner);
+        Self::require_not_cancelled(&campaign);
+
+        if env.ledger().timestamp() <= campaign.deadline || campaign.raised < campaign.goal {
+            panic!("Only a successful campaign can be abandoned");
+        }
+        if campaign.abandoned {
+            panic!("Campaign has been abandoned");
+        }
+
+        if campaign.milestones.is_empty() {
+            panic!("Campaign has no milestones");
         }
+        if campaign.milestones_released >= campaign.milestones.len() {
+            panic!("All milestones already released");
+        }
+
+        campaign.abandoned = true;
+        Self::write_campaign(&env, campaign_id, &campaign);
+    }
+
+    // Owner calls the campaign off before any funds were paid out. Donations
+    // and payouts stop and every donor can refund in full immediately.
+    pub fn cancel_campaign(env: Env, campaign_id: u32, owner: Address, reason_hash: BytesN<32>) {
+        owner.require_auth();
+
+        let mut campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_owner(&campaign, &owner);
+        Self::require_not_cancelled(&campaign);
+
+        if campaign.withdrawn > 0 || campaign.milestones_released > 0 {
+            panic!("Campaign funds already paid out");
+        }
+
+        campaign.cancel_reason = Some(reason_hash.clone());
+        Self::write_campaign(&env, campaign_id, &campaign);
 
-        let xlm_token_address: Address = env.storage().instance().get(&XLM_TOKEN_ADDRESS).unwrap();
-        let xlm_token = token::Client::new(&env, &xlm_token_address);
+        Cancelled { campaign_id, owner, reason_hash }.publish(&env);
+    }
+
+    pub fn is_cancelled(env: Env, campaign_id: u32) -> bool {
+        Self::get_campaign(env, campaign_id).is_some_and(|campaign| campaign.cancel_reason.is_some())
+    }
+
+    // Hash of the reason given when the campaign was cancelled
+    pub fn get_cancel_reason(env: Env, campaign_id: u32) -> Option<BytesN<32>> {
+        Self::get_campaign(env, campaign_id)?.cancel_reason
+    }
+
+    pub fn get_milestones(env: Env, campaign_id: u32) -> Vec<Milestone> {
+        Self::get_campaign(env.clone(), campaign_id)
+            .map_or(Vec::new(&env), |campaign| campaign.milestones)
+    }
+
+    // Number of milestones paid out so far
+    pub fn get_released_milestones(env: Env, campaign_id: u32) -> u32 {
+        Self::get_campaign(env, campaign_id).map_or(0, |campaign| campaign.milestones_released)
+    }
+
+    pub fn get_released_amount(env: Env, campaign_id: u32) -> i128 {
+        Self::get_campaign(env, campaign_id).map_or(0, |campaign| campaign.released)
+    }
+
+    pub fn is_abandoned(env: Env, campaign_id: u32) -> bool {
+        Self::get_campaign(env, campaign_id).is_some_and(|campaign| campaign.abandoned)
+    }
+
+    fn register_campaign(
+        env: &Env,
+        owner: Address,
+        goal: i128,
+        deadline: u64,
+        token: Address,
+        milestones: Vec<Milestone>,
+        platform_fee: Option<PlatformFee>,
+    ) -> u32 {
+        owner.require_auth();
+
+        if goal <= 0 {
+            panic!("Goal must be positive");
+        }
+        if deadline <= env.ledger().timestamp() {
+            panic!("Deadline must be in the future");
+        }
+
+        // Milestones are optional, but when given they must cover all funds
+        if !milestones.is_empty() {
+            let mut total_share: u32 = 0;
+            for milestone in milestones.iter() {
+                if milestone.share_bps == 0 {
+                    panic!("Milestone share must be positive");
+                }
+                total_share += milestone.share_bps;
+            }
+            if total_share != TOTAL_SHARE_BPS {
+                panic!("Milestone shares must add up to 100%");
+            }
+        }
+
+        let campaign_id = Self::get_campaign_count(env.clone());
+        let campaign = Campaign {
+            owner: owner.clone(),
+            goal,
+            deadline,
+            token,
+            raised: 0,
+            milestones,
+            milestones_released: 0,
+            released: 0,
+            abandoned: false,
+            withdrawn: 0,
+            cancel_reason: None,
+            treasury: platform_fee.as_ref().map(|fee| fee.treasury.clone()),
+            fee_bps: platform_fee.map_or(0, |fee| fee.fee_bps),
+        };
+        Self::write_campaign(env, campaign_id, &campaign);
+        env.storage().instance().set(&DataKey::CampaignCount, &(campaign_id + 1));
+
+        let mut owned = Self::get_campaigns_by_owner(env.clone(), owner.clone());
+        owned.push_back(campaign_id);
+        Self::write_entry(env, &DataKey::OwnerCampaigns(owner), &owned);
+
+        campaign_id
+    }
+
+    // Pull `amount` from an already authorized donor into the campaign
+    fn accept_donation(env: &Env, campaign_id: u32, donor: &Address, amount: i128) {
+        let campaign = Self::read_campaign(env, campaign_id);
+        Self::require_open(env, &campaign);
+
+        if amount <= 0 {
+            panic!("Donation amount must be positive");
+        }
+
+        // Only the part that fits under the hard cap is taken
+        let amount = Self::apply_limits(env, campaign_id, &campaign, donor, amount);
+
+        let token_client = token::Client::new(env, &campaign.token);
         let contract_address = env.current_contract_address();
 
-        xlm_token.transfer(&contract_address, &donor, &donated_amount);
+        token_client.transfer(donor, &contract_address, &amount);
+
+        let token = campaign.token.clone();
+        Self::record_donation(env, campaign_id, campaign, donor.clone(), token, amount, amount);
+    }
+
+    // Book `amount` of `token` for the donor; `value` is what it is worth in
+    // campaign token units and counts towards the goal together with any
+    // sponsor match it draws
+    fn record_donation(
+        env: &Env,
+        campaign_id: u32,
+        mut campaign: Campaign,
+        donor: Address,
+        token: Address,
+        amount: i128,
+        value: i128,
+    ) {
+        // Every donation path ends here, so invite-only campaigns are enforced once
+        Self::require_allowed(env, campaign_id, &donor);
 
-        let mut total: i128 = env.storage().instance().get(&TOTAL_RAISED).unwrap();
-        total -= donated_amount;
-        env.storage().instance().set(&TOTAL_RAISED, &total);
+        let room = Self::cap_room(env, campaign_id, &campaign, value);
+        let matched = Self::draw_match(env, campaign_id, &campaign, &donor, value, room);
+        campaign.raised += value + matched;
+        Self::write_campaign(env, campaign_id, &campaign);
 
-        donations.set(donor, 0);
-        env.storage().instance().set(&DONATIONS, &donations);
-        
-        donated_amount
+        Self::record_token_donation(env, campaign_id, &donor, &token, amount);
+
+        let total_donation = Self::get_donation(env.clone(), campaign_id, donor.clone()) + value;
+        Self::write_entry(env, &DataKey::Donation(campaign_id, donor.clone()), &total_donation);
+
+        Self::issue_receipt(env, campaign_id, &donor, total_donation);
     }
 
-    // ✅ NEW: Helper function to check initialization
-    fn require_initialized(env: &Env) {
-        let is_init: bool = env.storage().instance().get(&IS_ALREADY_INIT).unwrap_or(false);
-        if !is_init {
-            panic!("Contract not initialized");
+    // Sum of the shares of the milestones already paid out
+    fn released_share_bps(campaign: &Campaign) -> u32 {
+        campaign.milestones.iter()
+            .take(campaign.milestones_released as usize)
+            .map(|milestone| milestone.share_bps)
+            .sum()
+    }
+
+    // Donations are only taken while the campaign runs and was not cancelled
+    fn require_open(env: &Env, campaign: &Campaign) {
+        Self::require_not_cancelled(campaign);
+        if env.ledger().timestamp() > campaign.deadline {
+            panic!("Campaign has ended");
         }
     }
+
+    fn require_not_cancelled(campaign: &Campaign) {
+        if campaign.cancel_reason.is_some() {
+            panic!("Campaign has been cancelled");
+        }
+    }
+
+    fn require_owner(campaign: &Campaign, caller: &Address) {
+        if *caller != campaign.owner {
+            panic!("Only the campaign owner can do this");
+        }
+    }
+
+    fn read_campaign(env: &Env, campaign_id: u32) -> Campaign {
+        Self::get_campaign(env.clone(), campaign_id).unwrap_or_else(|| panic!("Campaign not found"))
+    }
+
+    fn write_campaign(env: &Env, campaign_id: u32, campaign: &Campaign) {
+        Self::write_entry(env, &DataKey::Campaign(campaign_id), campaign);
+    }
+
+    // Store a persistent entry and keep it alive
+    pub(crate) fn write_entry<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+        env.storage().persistent().set(key, value);
+        env.storage().persistent().extend_ttl(key, ENTRY_TTL_THRESHOLD, ENTRY_TTL_EXTEND);
+    }
 }
 
 #[cfg(test)]
-mod test;
\ No newline at end of file
+mod test;
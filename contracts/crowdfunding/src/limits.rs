@@ -0,0 +1,85 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Env};
+
+use crate::{Campaign, CrowdfundingContract, CrowdfundingContractArgs, CrowdfundingContractClient, DataKey};
+
+// Optional bounds on donations, all in campaign token units. Donations past
+// the hard cap are cut down to what still fits.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DonationLimits {
+    pub min_donation: Option<i128>,
+    pub max_per_donor: Option<i128>,
+    pub hard_cap: Option<i128>,
+}
+
+#[contractimpl]
+impl CrowdfundingContract {
+
+    // Owner sets the campaign's donation bounds before the first donation
+    pub fn set_donation_limits(env: Env, campaign_id: u32, owner: Address, limits: DonationLimits) {
+        owner.require_auth();
+
+        let campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_owner(&campaign, &owner);
+        Self::require_open(&env, &campaign);
+
+        if campaign.raised > 0 {
+            panic!("Limits must be set before the first donation");
+        }
+        if limits.min_donation.is_some_and(|min| min <= 0)
+            || limits.max_per_donor.is_some_and(|max| max <= 0)
+        {
+            panic!("Donation limits must be positive");
+        }
+        if let (Some(min), Some(max)) = (limits.min_donation, limits.max_per_donor) {
+            if min > max {
+                panic!("Minimum donation is above the per-donor maximum");
+            }
+        }
+        if limits.hard_cap.is_some_and(|cap| cap < campaign.goal) {
+            panic!("Hard cap must not be below the goal");
+        }
+
+        Self::write_entry(&env, &DataKey::DonationLimits(campaign_id), &limits);
+    }
+
+    pub fn get_donation_limits(env: Env, campaign_id: u32) -> DonationLimits {
+        env.storage().persistent().get(&DataKey::DonationLimits(campaign_id)).unwrap_or_default()
+    }
+
+    // What can still be raised before the hard cap, None without a cap
+    pub fn get_remaining_capacity(env: Env, campaign_id: u32) -> Option<i128> {
+        let campaign = Self::get_campaign(env.clone(), campaign_id)?;
+        let hard_cap = Self::get_donation_limits(env, campaign_id).hard_cap?;
+        Some((hard_cap - campaign.raised).max(0))
+    }
+
+    // Check a donation worth `value` against the limits and return the part
+    // of it that fits under the hard cap
+    pub(crate) fn apply_limits(env: &Env, campaign_id: u32, campaign: &Campaign, donor: &Address, value: i128) -> i128 {
+        let limits = Self::get_donation_limits(env.clone(), campaign_id);
+
+        if limits.min_donation.is_some_and(|min| value < min) {
+            panic!("Donation below campaign minimum");
+        }
+
+        let donated = Self::get_donation(env.clone(), campaign_id, donor.clone());
+        if limits.max_per_donor.is_some_and(|max| donated + value > max) {
+            panic!("Donation above per-donor maximum");
+        }
+
+        match limits.hard_cap {
+            Some(hard_cap) if campaign.raised >= hard_cap => panic!("Hard cap reached"),
+            Some(hard_cap) => value.min(hard_cap - campaign.raised),
+            None => value,
+        }
+    }
+
+    // Room left under the hard cap once `value` is added, for sponsor matches
+    pub(crate) fn cap_room(env: &Env, campaign_id: u32, campaign: &Campaign, value: i128) -> i128 {
+        match Self::get_donation_limits(env.clone(), campaign_id).hard_cap {
+            Some(hard_cap) => (hard_cap - campaign.raised - value).max(0),
+            None => i128::MAX,
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use soroban_sdk::{contractimpl, contracttype, token, Address, Env};
+
+use crate::{
+    Campaign, CrowdfundingContract, CrowdfundingContractArgs, CrowdfundingContractClient, DataKey, TOTAL_SHARE_BPS,
+};
+
+// Sponsor funds in the campaign token that top up every donation. `ratio` is
+// in basis points of the donation, so 10_000 matches one to one.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchPool {
+    pub sponsor: Address,
+    pub available: i128,
+    pub matched: i128,
+    pub ratio: u32,
+    pub cap_per_donor: i128,
+}
+
+#[contractimpl]
+impl CrowdfundingContract {
+
+    // Sponsor locks `amount` of the campaign token to match later donations
+    pub fn add_match_pool(
+        env: Env,
+        campaign_id: u32,
+        sponsor: Address,
+        amount: i128,
+        ratio: u32,
+        cap_per_donor: i128,
+    ) {
+        sponsor.require_auth();
+
+        let campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_open(&env, &campaign);
+
+        if amount <= 0 {
+            panic!("Pool amount must be positive");
+        }
+        if ratio == 0 {
+            panic!("Match ratio must be positive");
+        }
+        if cap_per_donor <= 0 {
+            panic!("Match cap must be positive");
+        }
+
+        if Self::get_match_pool(env.clone(), campaign_id).is_some() {
+            panic!("Campaign already has a match pool");
+        }
+
+        let token_client = token::Client::new(&env, &campaign.token);
+        let contract_address = env.current_contract_address();
+        token_client.transfer(&sponsor, &contract_address, &amount);
+
+        let pool = MatchPool { sponsor, available: amount, matched: 0, ratio, cap_per_donor };
+        Self::write_match_pool(&env, campaign_id, &pool);
+    }
+
+    // Sponsor takes back what was never drawn once the campaign is over.
+    // Funds returned by later refunds can be reclaimed with another call.
+    pub fn reclaim_match_pool(env: Env, campaign_id: u32, sponsor: Address) -> i128 {
+        sponsor.require_auth();
+
+        let campaign = Self::read_campaign(&env, campaign_id);
+        let mut pool = Self::get_match_pool(env.clone(), campaign_id)
+            .unwrap_or_else(|| panic!("Campaign has no match pool"));
+
+        if sponsor != pool.sponsor {
+            panic!("Only the sponsor can reclaim the pool");
+        }
+        if env.ledger().timestamp() <= campaign.deadline && campaign.cancel_reason.is_none() {
+            panic!("Campaign belum berakhir");
+        }
+
+        let amount = pool.available;
+        pool.available = 0;
+        Self::write_match_pool(&env, campaign_id, &pool);
+
+        if amount > 0 {
+            let token_client = token::Client::new(&env, &campaign.token);
+            token_client.transfer(&env.current_contract_address(), &sponsor, &amount);
+        }
+
+        amount
+    }
+
+    pub fn get_match_pool(env: Env, campaign_id: u32) -> Option<MatchPool> {
+        env.storage().persistent().get(&DataKey::MatchPool(campaign_id))
+    }
+
+    // Sponsor funds drawn by the donor's donations
+    pub fn get_matched(env: Env, campaign_id: u32, donor: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::Matched(campaign_id, donor)).unwrap_or(0)
+    }
+
+    // What donors gave the campaign themselves, without sponsor matches. Donor
+    // votes are weighed against this, since matched funds carry no vote.
+    pub(crate) fn donor_raised(env: &Env, campaign_id: u32, campaign: &Campaign) -> i128 {
+        let matched = Self::get_match_pool(env.clone(), campaign_id).map_or(0, |pool| pool.matched);
+        campaign.raised - matched
+    }
+
+    // Move the match for a donation worth `value` from the pool into the
+    // campaign, at most `room`, returns the amount matched
+    pub(crate) fn draw_match(
+        env: &Env,
+        campaign_id: u32,
+        campaign: &Campaign,
+        donor: &Address,
+        value: i128,
+        room: i128,
+    ) -> i128 {
+        let mut pool = match Self::get_match_pool(env.clone(), campaign_id) {
+            Some(pool) => pool,
+            None => return 0,
+        };
+
+        let already = Self::get_matched(env.clone(), campaign_id, donor.clone());
+        let amount = (value * pool.ratio as i128 / TOTAL_SHARE_BPS as i128)
+            .min(pool.cap_per_donor - already)
+            .min(pool.available)
+            .min(room);
+        if amount <= 0 {
+            return 0;
+        }
+
+        pool.available -= amount;
+        pool.matched += amount;
+        Self::write_match_pool(env, campaign_id, &pool);
+
+        Self::write_entry(env, &DataKey::Matched(campaign_id, donor.clone()), &(already + amount));
+
+        Self::adjust_token_total(env, campaign_id, &campaign.token, amount);
+
+        amount
+    }
+
+    // Clear the donor's match and put `returned` of it back in the pool,
+    // returns the donor's whole match
+    pub(crate) fn reverse_match(env: &Env, campaign_id: u32, donor: &Address, returned: impl Fn(i128) -> i128) -> i128 {
+        let amount = Self::get_matched(env.clone(), campaign_id, donor.clone());
+        if amount == 0 {
+            return 0;
+        }
+
+        env.storage().persistent().remove(&DataKey::Matched(campaign_id, donor.clone()));
+
+        let mut pool = Self::get_match_pool(env.clone(), campaign_id).unwrap();
+        pool.available += returned(amount);
+        pool.matched -= amount;
+        Self::write_match_pool(env, campaign_id, &pool);
+
+        amount
+    }
+
+    fn write_match_pool(env: &Env, campaign_id: u32, pool: &MatchPool) {
+        Self::write_entry(env, &DataKey::MatchPool(campaign_id), pool);
+    }
+}
@@ -0,0 +1,84 @@
+use soroban_sdk::{contractclient, contractimpl, contracttype, Address, Env, Vec};
+
+use crate::{CrowdfundingContract, CrowdfundingContractArgs, CrowdfundingContractClient, DataKey};
+
+// Soulbound proof that `amount` was donated to a campaign. Receipts cannot be
+// transferred; they follow the donation and are burned when it is refunded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Receipt {
+    pub campaign_id: u32,
+    pub amount: i128,
+    pub issued_at: u64,
+}
+
+// Lets other contracts gate features on backing a campaign without depending
+// on this crate
+#[contractclient(name = "DonorReceiptsClient")]
+pub trait DonorReceipts {
+    fn has_backed(env: Env, campaign_id: u32, backer: Address) -> bool;
+}
+
+#[contractimpl]
+impl CrowdfundingContract {
+
+    // All live receipts held by `donor`, one per campaign backed
+    pub fn receipts_of(env: Env, donor: Address) -> Vec<Receipt> {
+        let mut receipts = Vec::new(&env);
+        for campaign_id in Self::read_backed_campaigns(&env, &donor).iter() {
+            if let Some(receipt) = Self::read_receipt(&env, campaign_id, &donor) {
+                receipts.push_back(receipt);
+            }
+        }
+        receipts
+    }
+
+    // Whether `backer` holds a receipt for the campaign
+    pub fn has_backed(env: Env, campaign_id: u32, backer: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Receipt(campaign_id, backer))
+    }
+
+    // Issue or top up the donor's receipt to match their total donation
+    pub(crate) fn issue_receipt(env: &Env, campaign_id: u32, donor: &Address, amount: i128) {
+        let receipt = match Self::read_receipt(env, campaign_id, donor) {
+            Some(receipt) => Receipt { amount, ..receipt },
+            None => {
+                let mut backed = Self::read_backed_campaigns(env, donor);
+                backed.push_back(campaign_id);
+                Self::write_entry(env, &DataKey::BackedCampaigns(donor.clone()), &backed);
+
+                Receipt { campaign_id, amount, issued_at: env.ledger().timestamp() }
+            }
+        };
+        Self::write_entry(env, &DataKey::Receipt(campaign_id, donor.clone()), &receipt);
+    }
+
+    // Burn the donor's receipt, called when the donation is refunded
+    pub(crate) fn burn_receipt(env: &Env, campaign_id: u32, donor: &Address) {
+        if Self::read_receipt(env, campaign_id, donor).is_none() {
+            return;
+        }
+        env.storage().persistent().remove(&DataKey::Receipt(campaign_id, donor.clone()));
+
+        let mut backed = Self::read_backed_campaigns(env, donor);
+        if let Some(index) = backed.first_index_of(campaign_id) {
+            backed.remove(index);
+        }
+
+        let key = DataKey::BackedCampaigns(donor.clone());
+        if backed.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            Self::write_entry(env, &key, &backed);
+        }
+    }
+
+    fn read_receipt(env: &Env, campaign_id: u32, donor: &Address) -> Option<Receipt> {
+        env.storage().persistent().get(&DataKey::Receipt(campaign_id, donor.clone()))
+    }
+
+    // Ids of the campaigns the donor holds a receipt for, in issue order
+    fn read_backed_campaigns(env: &Env, donor: &Address) -> Vec<u32> {
+        env.storage().persistent().get(&DataKey::BackedCampaigns(donor.clone())).unwrap_or(Vec::new(env))
+    }
+}
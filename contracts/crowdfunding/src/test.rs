@@ -1,9 +1,12 @@
 #![cfg(test)]
+extern crate std;
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    token, Address, Env,
+    contract, contractimpl, symbol_short,
+    events::Event,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, xdr::ToXdr, Address, Env, String,
 };
 
 // Helper function to create a mock token contract for testing
@@ -12,9 +15,9 @@ fn create_token_contract<'a>(env: &Env, admin: &Address) -> token::StellarAssetC
     token::StellarAssetClient::new(env, &token_address.address())
 }
 
-// Test 1: Initialize campaign successfully
+// Test 1: Create campaign successfully
 #[test]
-fn test_initialize_campaign() {
+fn test_create_campaign() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -30,14 +33,15 @@ fn test_initialize_campaign() {
     let token = create_token_contract(&env, &token_admin);
     let token_address = token.address.clone();
 
-    // Initialize campaign
-    client.initialize(&owner, &goal, &deadline, &token_address);
+    // Create campaign
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token_address, &Vec::new(&env));
 
-    // Verify campaign initialized correctly
-    assert_eq!(client.get_total_raised(), 0);
-    assert_eq!(client.get_goal(), goal);
-    assert_eq!(client.get_deadline(), deadline);
-    assert_eq!(client.get_is_already_init(), true);
+    // Verify campaign created correctly
+    assert_eq!(client.get_total_raised(&campaign_id), 0);
+    assert_eq!(client.get_goal(&campaign_id), goal);
+    assert_eq!(client.get_deadline(&campaign_id), deadline);
+    assert_eq!(campaign_id, 0);
+    assert_eq!(client.get_campaign_count(), 1);
 }
 
 // Test 2: Get donation for address that never donated
@@ -58,10 +62,10 @@ fn test_get_donation_no_donation() {
     let token = create_token_contract(&env, &token_admin);
 
     // Initialize campaign
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
 
     // Check donation amount for address that never donated
-    assert_eq!(client.get_donation(&non_donor), 0);
+    assert_eq!(client.get_donation(&campaign_id, &non_donor), 0);
 }
 
 // Test 3: Cannot donate zero amount
@@ -82,10 +86,10 @@ fn test_donate_zero_amount() {
     let token_admin = Address::generate(&env);
     let token = create_token_contract(&env, &token_admin);
 
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
 
     // Try to donate 0 - should panic
-    client.donate(&donor, &0);
+    client.donate(&campaign_id, &donor, &0);
 }
 
 // Test 4: Cannot donate negative amount
@@ -106,10 +110,10 @@ fn test_donate_negative_amount() {
     let token_admin = Address::generate(&env);
     let token = create_token_contract(&env, &token_admin);
 
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
 
     // Try to donate negative amount - should panic
-    client.donate(&donor, &-100_000_000);
+    client.donate(&campaign_id, &donor, &-100_000_000);
 }
 
 // Test 5: Campaign deadline validation
@@ -130,7 +134,7 @@ fn test_donate_after_deadline() {
     let token_admin = Address::generate(&env);
     let token = create_token_contract(&env, &token_admin);
 
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
 
     // Fast forward time past deadline
     env.ledger().with_mut(|li| {
@@ -138,49 +142,57 @@ fn test_donate_after_deadline() {
     });
 
     // This should panic
-    client.donate(&donor, &100_000_000);
+    client.donate(&campaign_id, &donor, &100_000_000);
 }
 
-// Test 6: Check initialization status before initialization
+// Test 6: No campaigns before any is created
 #[test]
-fn test_is_already_init_before_initialization() {
+fn test_no_campaigns_before_creation() {
     let env = Env::default();
     let contract_id = env.register(CrowdfundingContract, ());
     let client = CrowdfundingContractClient::new(&env, &contract_id);
 
-    // Before initialization, should return false
-    assert_eq!(client.get_is_already_init(), false);
+    // Unknown campaigns read as empty
+    assert_eq!(client.get_campaign_count(), 0);
+    assert_eq!(client.get_campaign(&0), None);
+    assert_eq!(client.get_goal(&0), 0);
+    assert_eq!(client.list_campaigns(&0, &10).len(), 0);
 }
 
-// Test 7: Check initialization status after initialization
+// Test 7: Campaigns get sequential ids and can be listed per owner
 #[test]
-fn test_is_already_init_after_initialization() {
+fn test_list_campaigns_and_by_owner() {
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register(CrowdfundingContract, ());
     let client = CrowdfundingContractClient::new(&env, &contract_id);
 
-    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
     let goal = 900_000_000i128;
     let deadline = env.ledger().timestamp() + 86400;
-    
+
     let token_admin = Address::generate(&env);
     let token = create_token_contract(&env, &token_admin);
 
-    // Before initialization
-    assert_eq!(client.get_is_already_init(), false);
+    assert_eq!(client.create_campaign(&alice, &goal, &deadline, &token.address, &Vec::new(&env)), 0);
+    assert_eq!(client.create_campaign(&bob, &goal, &deadline, &token.address, &Vec::new(&env)), 1);
+    assert_eq!(client.create_campaign(&alice, &goal, &deadline, &token.address, &Vec::new(&env)), 2);
 
-    // Initialize the contract
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    assert_eq!(client.get_campaign_count(), 3);
+    assert_eq!(client.list_campaigns(&0, &10), vec![&env, 0, 1, 2]);
+    assert_eq!(client.list_campaigns(&1, &1), vec![&env, 1]);
+    assert_eq!(client.list_campaigns(&5, &10).len(), 0);
 
-    // After initialization, should return true
-    assert_eq!(client.get_is_already_init(), true);
+    assert_eq!(client.get_campaigns_by_owner(&alice), vec![&env, 0, 2]);
+    assert_eq!(client.get_campaigns_by_owner(&bob), vec![&env, 1]);
+    assert_eq!(client.get_campaign(&1).unwrap().owner, bob);
 }
 
-// Test 8: Initialization flag persists after other operations
+// Test 8: Donations are tracked per campaign
 #[test]
-fn test_is_already_init_persists() {
+fn test_donations_are_separate_per_campaign() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -189,27 +201,72 @@ fn test_is_already_init_persists() {
 
     let owner = Address::generate(&env);
     let donor = Address::generate(&env);
-    let goal = 900_000_000i128;
-    let deadline = env.ledger().timestamp() + 86400;
-    
+    let goal = 100_000_000i128;
+    let deadline = env.ledger().timestamp() + 100;
+
     let token_admin = Address::generate(&env);
     let token = create_token_contract(&env, &token_admin);
+    token.mint(&donor, &200_000_000);
+
+    let first = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
+    let second = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
+
+    client.donate(&first, &donor, &120_000_000);
+    client.donate(&second, &donor, &30_000_000);
+
+    assert_eq!(client.get_donation(&first, &donor), 120_000_000);
+    assert_eq!(client.get_donation(&second, &donor), 30_000_000);
+    assert!(client.is_goal_reached(&first));
+    assert!(!client.is_goal_reached(&second));
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+
+    // Only the failed campaign refunds, and only its own donation
+    assert!(client.try_refund(&first, &donor).is_err());
+    assert_eq!(client.refund(&second, &donor), 30_000_000);
+    assert_eq!(client.get_total_raised(&first), 120_000_000);
+}
+
+// Test 9: Each campaign, donation and owner index is its own persistent entry
+#[test]
+fn test_campaign_state_in_persistent_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let donor = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 100;
 
-    // Initialize the contract
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    token.mint(&donor, &50_000_000);
 
-    // Verify it's initialized
-    assert_eq!(client.get_is_already_init(), true);
+    let campaign_id = client.create_campaign(&owner, &100_000_000, &deadline, &token.address, &Vec::new(&env));
+    client.donate(&campaign_id, &donor, &50_000_000);
 
-    // Check after querying other functions
-    let _ = client.get_total_raised();
-    let _ = client.get_donation(&donor);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        assert_eq!(storage.get::<_, Campaign>(&DataKey::Campaign(campaign_id)).unwrap().raised, 50_000_000);
+        assert_eq!(storage.get::<_, i128>(&DataKey::Donation(campaign_id, donor.clone())), Some(50_000_000));
+        assert_eq!(storage.get::<_, Vec<u32>>(&DataKey::OwnerCampaigns(owner.clone())), Some(vec![&env, campaign_id]));
+    });
 
-    // Should still be true
-    assert_eq!(client.get_is_already_init(), true);
+    // A refund frees the donor's entry
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    client.refund(&campaign_id, &donor);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&DataKey::Donation(campaign_id, donor.clone())));
+    });
 }
 
-// Test 9: Get goal returns correct value
+// Test 10: Get goal returns correct value
 #[test]
 fn test_get_goal() {
     let env = Env::default();
@@ -225,13 +282,13 @@ fn test_get_goal() {
     let token_admin = Address::generate(&env);
     let token = create_token_contract(&env, &token_admin);
 
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
 
     // Test get_goal returns correct value
-    assert_eq!(client.get_goal(), goal);
+    assert_eq!(client.get_goal(&campaign_id), goal);
 }
 
-// Test 10: Get deadline returns correct value
+// Test 11: Get deadline returns correct value
 #[test]
 fn test_get_deadline() {
     let env = Env::default();
@@ -247,13 +304,13 @@ fn test_get_deadline() {
     let token_admin = Address::generate(&env);
     let token = create_token_contract(&env, &token_admin);
 
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
 
     // Test get_deadline returns correct value
-    assert_eq!(client.get_deadline(), deadline);
+    assert_eq!(client.get_deadline(&campaign_id), deadline);
 }
 
-// Test 11: Is goal reached - full lifecycle
+// Test 12: Is goal reached - full lifecycle
 #[test]
 fn test_is_goal_reached() {
     let env = Env::default();
@@ -274,25 +331,25 @@ fn test_is_goal_reached() {
     // Mint tokens to donor for testing
     token.mint(&donor, &100_000_000); // 10 XLM
 
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
 
     // Before donation - should be false
-    assert_eq!(client.is_goal_reached(), false);
+    assert!(!client.is_goal_reached(&campaign_id));
 
     // Donate less than goal
-    client.donate(&donor, &30_000_000); // 3 XLM
-    assert_eq!(client.is_goal_reached(), false);
+    client.donate(&campaign_id, &donor, &30_000_000); // 3 XLM
+    assert!(!client.is_goal_reached(&campaign_id));
 
     // Donate to reach goal
-    client.donate(&donor, &20_000_000); // 2 XLM more
-    assert_eq!(client.is_goal_reached(), true);
+    client.donate(&campaign_id, &donor, &20_000_000); // 2 XLM more
+    assert!(client.is_goal_reached(&campaign_id));
 
     // Donate more than goal
-    client.donate(&donor, &10_000_000); // 1 XLM more
-    assert_eq!(client.is_goal_reached(), true); // Still true
+    client.donate(&campaign_id, &donor, &10_000_000); // 1 XLM more
+    assert!(client.is_goal_reached(&campaign_id)); // Still true
 }
 
-// Test 12: Is ended - timeline check
+// Test 13: Is ended - timeline check
 #[test]
 fn test_is_ended() {
     let env = Env::default();
@@ -308,25 +365,25 @@ fn test_is_ended() {
     let token_admin = Address::generate(&env);
     let token = create_token_contract(&env, &token_admin);
 
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
 
     // Before deadline
-    assert_eq!(client.is_ended(), false);
+    assert!(!client.is_ended(&campaign_id));
 
     // Fast forward time to deadline
     env.ledger().with_mut(|li| {
         li.timestamp = deadline;
     });
-    assert_eq!(client.is_ended(), false); // Exactly at deadline = not ended
+    assert!(!client.is_ended(&campaign_id)); // Exactly at deadline = not ended
 
     // Fast forward past deadline
     env.ledger().with_mut(|li| {
         li.timestamp = deadline + 1;
     });
-    assert_eq!(client.is_ended(), true); // Now it's ended
+    assert!(client.is_ended(&campaign_id)); // Now it's ended
 }
 
-// Test 13: Get progress percentage
+// Test 14: Get progress percentage
 #[test]
 fn test_get_progress_percentage() {
     let env = Env::default();
@@ -347,29 +404,29 @@ fn test_get_progress_percentage() {
     // Mint tokens to donor
     token.mint(&donor, &200_000_000); // 20 XLM
 
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
 
     // 0% progress
-    assert_eq!(client.get_progress_percentage(), 0);
+    assert_eq!(client.get_progress_percentage(&campaign_id).goal_percentage, 0);
 
     // Donate 25% of goal
-    client.donate(&donor, &25_000_000); // 2.5 XLM
-    assert_eq!(client.get_progress_percentage(), 25);
+    client.donate(&campaign_id, &donor, &25_000_000); // 2.5 XLM
+    assert_eq!(client.get_progress_percentage(&campaign_id).goal_percentage, 25);
 
     // Donate to reach 50%
-    client.donate(&donor, &25_000_000); // 2.5 XLM more
-    assert_eq!(client.get_progress_percentage(), 50);
+    client.donate(&campaign_id, &donor, &25_000_000); // 2.5 XLM more
+    assert_eq!(client.get_progress_percentage(&campaign_id).goal_percentage, 50);
 
     // Donate to reach 100%
-    client.donate(&donor, &50_000_000); // 5 XLM more
-    assert_eq!(client.get_progress_percentage(), 100);
+    client.donate(&campaign_id, &donor, &50_000_000); // 5 XLM more
+    assert_eq!(client.get_progress_percentage(&campaign_id).goal_percentage, 100);
 
     // Donate more than goal - 120%
-    client.donate(&donor, &20_000_000); // 2 XLM more
-    assert_eq!(client.get_progress_percentage(), 120);
+    client.donate(&campaign_id, &donor, &20_000_000); // 2 XLM more
+    assert_eq!(client.get_progress_percentage(&campaign_id).goal_percentage, 120);
 }
 
-// Test 14: Refund success scenario
+// Test 15: Refund success scenario
 #[test]
 fn test_refund_success() {
     let env = Env::default();
@@ -390,15 +447,15 @@ fn test_refund_success() {
     // Mint tokens to donor
     token.mint(&donor, &50_000_000); // 5 XLM
 
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
 
     // Make donation
     let donation_amount = 30_000_000i128; // 3 XLM
-    client.donate(&donor, &donation_amount);
+    client.donate(&campaign_id, &donor, &donation_amount);
 
     // Verify donation recorded
-    assert_eq!(client.get_donation(&donor), donation_amount);
-    assert_eq!(client.get_total_raised(), donation_amount);
+    assert_eq!(client.get_donation(&campaign_id, &donor), donation_amount);
+    assert_eq!(client.get_total_raised(&campaign_id), donation_amount);
 
     // Fast forward past deadline (goal not reached)
     env.ledger().with_mut(|li| {
@@ -406,15 +463,15 @@ fn test_refund_success() {
     });
 
     // Refund
-    let refunded = client.refund(&donor);
+    let refunded = client.refund(&campaign_id, &donor);
 
     // Verify refund
     assert_eq!(refunded, donation_amount);
-    assert_eq!(client.get_donation(&donor), 0);
-    assert_eq!(client.get_total_raised(), 0);
+    assert_eq!(client.get_donation(&campaign_id, &donor), 0);
+    assert_eq!(client.get_total_raised(&campaign_id), 0);
 }
 
-// Test 15: Cannot refund before deadline
+// Test 16: Cannot refund before deadline
 #[test]
 #[should_panic(expected = "Campaign belum berakhir")]
 fn test_refund_before_deadline() {
@@ -436,14 +493,14 @@ fn test_refund_before_deadline() {
     // Mint tokens to donor
     token.mint(&donor, &50_000_000);
 
-    client.initialize(&owner, &goal, &deadline, &token.address);
-    client.donate(&donor, &30_000_000);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
+    client.donate(&campaign_id, &donor, &30_000_000);
 
     // Try refund before deadline - should panic
-    client.refund(&donor);
+    client.refund(&campaign_id, &donor);
 }
 
-// Test 16: Cannot refund when goal reached
+// Test 17: Cannot refund when goal reached
 #[test]
 #[should_panic(expected = "Goal sudah tercapai, tidak bisa refund")]
 fn test_refund_when_goal_reached() {
@@ -465,10 +522,10 @@ fn test_refund_when_goal_reached() {
     // Mint tokens to donor
     token.mint(&donor, &goal);
 
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
 
     // Donate exactly goal amount
-    client.donate(&donor, &goal);
+    client.donate(&campaign_id, &donor, &goal);
 
     // Fast forward past deadline
     env.ledger().with_mut(|li| {
@@ -476,10 +533,10 @@ fn test_refund_when_goal_reached() {
     });
 
     // Try refund when goal reached - should panic
-    client.refund(&donor);
+    client.refund(&campaign_id, &donor);
 }
 
-// Test 17: Cannot refund if no donations made
+// Test 18: Cannot refund if no donations made
 #[test]
 #[should_panic(expected = "No donations found for this address")]
 fn test_refund_no_donations() {
@@ -497,7 +554,7 @@ fn test_refund_no_donations() {
     let token_admin = Address::generate(&env);
     let token = create_token_contract(&env, &token_admin);
 
-    client.initialize(&owner, &goal, &deadline, &token.address);
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
 
     // Fast forward past deadline
     env.ledger().with_mut(|li| {
@@ -505,5 +562,1575 @@ fn test_refund_no_donations() {
     });
 
     // Try to refund without making donation - should panic
-    client.refund(&non_donor);
-}
\ No newline at end of file
+    client.refund(&campaign_id, &non_donor);
+}
+// Test 19: Donate in one call through the token's transfer_and_call
+#[test]
+fn test_donate_via_transfer_and_call() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let donor = Address::generate(&env);
+    let goal = 100_000_000i128;
+    let deadline = env.ledger().timestamp() + 86400;
+
+    // Campaign token is the repo's own TokenContract
+    let token_id = env.register(token_contract::TokenContract, ());
+    let token = token_contract::TokenContractClient::new(&env, &token_id);
+    token.initialize(
+        &donor,
+        &String::from_str(&env, "Test Token"),
+        &String::from_str(&env, "TST"),
+        &500_000_000,
+    );
+
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token_id, &Vec::new(&env));
+
+    // Transfer and donate in a single call
+    token.transfer_and_call(&donor, &contract_id, &30_000_000, &campaign_id.to_xdr(&env));
+
+    assert_eq!(client.get_donation(&campaign_id, &donor), 30_000_000);
+    assert_eq!(client.get_total_raised(&campaign_id), 30_000_000);
+    assert_eq!(token.balance(&contract_id), 30_000_000);
+}
+
+// Test 20: Only the campaign token can report incoming tokens
+#[test]
+fn test_on_token_received_rejects_direct_calls() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let goal = 100_000_000i128;
+    let deadline = env.ledger().timestamp() + 86400;
+
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &Vec::new(&env));
+
+    // Drop mocked auths: calling the hook directly must not credit a donation
+    env.set_auths(&[]);
+    let data = campaign_id.to_xdr(&env);
+    let result = client.try_on_token_received(&attacker, &50_000_000, &data);
+    assert!(result.is_err());
+    assert_eq!(client.get_total_raised(&campaign_id), 0);
+}
+
+// Helper to build a milestone with a recognizable description hash
+fn milestone(env: &Env, id: u8, share_bps: u32) -> Milestone {
+    Milestone {
+        description_hash: BytesN::from_array(env, &[id; 32]),
+        share_bps,
+    }
+}
+
+// Test 21: Milestones of a successful campaign are paid out in order
+#[test]
+fn test_release_milestones_in_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let donor = Address::generate(&env);
+    let goal = 100_000_000i128;
+    let deadline = env.ledger().timestamp() + 100;
+
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    token.mint(&donor, &100_000_001);
+
+    // 30% / 30% / 40%
+    let milestones = vec![
+        &env,
+        milestone(&env, 1, 3_000),
+        milestone(&env, 2, 3_000),
+        milestone(&env, 3, 4_000),
+    ];
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &milestones);
+    assert_eq!(client.get_milestones(&campaign_id), milestones);
+
+    client.donate(&campaign_id, &donor, &100_000_001);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+
+    // Every milestone after the first waits for donor approval
+    assert_eq!(client.release_milestone(&campaign_id, &owner), 30_000_000);
+    client.vote_milestone(&campaign_id, &donor, &true);
+    assert_eq!(client.release_milestone(&campaign_id, &owner), 30_000_000);
+    client.vote_milestone(&campaign_id, &donor, &true);
+    // The last milestone also picks up the rounding remainder
+    assert_eq!(client.release_milestone(&campaign_id, &owner), 40_000_001);
+
+    assert_eq!(client.get_released_milestones(&campaign_id), 3);
+    assert_eq!(client.get_released_amount(&campaign_id), 100_000_001);
+    assert_eq!(token_client.balance(&owner), 100_000_001);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    let result = client.try_release_milestone(&campaign_id, &owner);
+    assert!(result.is_err());
+}
+
+// Test 22: Milestones cannot be released while the campaign is running
+#[test]
+#[should_panic(expected = "Campaign belum berakhir")]
+fn test_release_milestone_before_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let donor = Address::generate(&env);
+    let goal = 100_000_000i128;
+    let deadline = env.ledger().timestamp() + 100;
+
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    token.mint(&donor, &100_000_000);
+
+    let milestones = vec![&env, milestone(&env, 1, 10_000)];
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &milestones);
+    client.donate(&campaign_id, &donor, &100_000_000);
+
+    // Should panic
+    client.release_milestone(&campaign_id, &owner);
+}
+
+// Test 23: A failed campaign releases nothing
+#[test]
+#[should_panic(expected = "Goal not reached")]
+fn test_release_milestone_goal_not_reached() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let donor = Address::generate(&env);
+    let goal = 100_000_000i128;
+    let deadline = env.ledger().timestamp() + 100;
+
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    token.mint(&donor, &50_000_000);
+
+    let milestones = vec![&env, milestone(&env, 1, 10_000)];
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &milestones);
+    client.donate(&campaign_id, &donor, &50_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+
+    // Should panic
+    client.release_milestone(&campaign_id, &owner);
+}
+
+// Test 24: Only the owner can release milestones
+#[test]
+#[should_panic(expected = "Only the campaign owner can do this")]
+fn test_only_owner_can_release_milestone() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let donor = Address::generate(&env);
+    let goal = 100_000_000i128;
+    let deadline = env.ledger().timestamp() + 100;
+
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    token.mint(&donor, &100_000_000);
+
+    let milestones = vec![&env, milestone(&env, 1, 10_000)];
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &milestones);
+    client.donate(&campaign_id, &donor, &100_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+
+    // Should panic
+    client.release_milestone(&campaign_id, &donor);
+}
+
+// Test 25: Abandoning a campaign refunds the unreleased funds pro-rata
+#[test]
+fn test_abandon_refunds_remaining_pro_rata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let donor1 = Address::generate(&env);
+    let donor2 = Address::generate(&env);
+    let goal = 100_000_000i128;
+    let deadline = env.ledger().timestamp() + 100;
+
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    token.mint(&donor1, &75_000_000);
+    token.mint(&donor2, &25_000_000);
+
+    let milestones = vec![&env, milestone(&env, 1, 4_000), milestone(&env, 2, 6_000)];
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &milestones);
+    client.donate(&campaign_id, &donor1, &75_000_000);
+    client.donate(&campaign_id, &donor2, &25_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+
+    // Goal reached, so no refunds until the owner abandons
+    assert!(client.try_refund(&campaign_id, &donor1).is_err());
+
+    assert_eq!(client.release_milestone(&campaign_id, &owner), 40_000_000);
+    client.abandon(&campaign_id, &owner);
+    assert!(client.is_abandoned(&campaign_id));
+
+    // 60% of every donation comes back
+    assert_eq!(client.refund(&campaign_id, &donor1), 45_000_000);
+    assert_eq!(client.refund(&campaign_id, &donor2), 15_000_000);
+    assert_eq!(token_client.balance(&donor1), 45_000_000);
+    assert_eq!(token_client.balance(&donor2), 15_000_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    // Nothing left to release
+    assert!(client.try_release_milestone(&campaign_id, &owner).is_err());
+}
+
+// Test 26: Milestone shares must cover exactly 100%
+#[test]
+#[should_panic(expected = "Milestone shares must add up to 100%")]
+fn test_create_campaign_with_invalid_milestone_shares() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let goal = 100_000_000i128;
+    let deadline = env.ledger().timestamp() + 100;
+
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+
+    let milestones = vec![&env, milestone(&env, 1, 5_000), milestone(&env, 2, 4_000)];
+
+    // Should panic
+    client.create_campaign(&owner, &goal, &deadline, &token.address, &milestones);
+}
+
+// A campaign built by setup_campaign. `token` is the campaign token and
+// every donor holds `donor_balance` of it (and of `other_token`, if any).
+struct CampaignSetup<'a> {
+    client: CrowdfundingContractClient<'a>,
+    token: token::StellarAssetClient<'a>,
+    other_token: Option<token::Client<'a>>,
+    owner: Address,
+    treasury: Address,
+    sponsor: Address,
+    donors: std::vec::Vec<Address>,
+    campaign_id: u32,
+    deadline: u64,
+}
+
+// What setup_campaign adds on top of a plain 100M campaign with three donors
+struct CampaignOptions {
+    goal: i128,
+    donor_balance: i128,
+    // Donated by donors[0] once everything else is set up
+    first_donation: i128,
+    milestones: std::vec::Vec<Milestone>,
+    // Paid to `treasury`
+    fee_bps: Option<u32>,
+    // Tier 0: 10 XLM, 1 slot; tier 1: 1 XLM, 5 slots
+    reward_tiers: bool,
+    // Whitelists `other_token` at this rate
+    other_token_rate: Option<i128>,
+    // 50M from `sponsor`, matched one to one and capped at 30M per donor
+    match_pool: bool,
+    limits: Option<DonationLimits>,
+}
+
+impl Default for CampaignOptions {
+    fn default() -> Self {
+        CampaignOptions {
+            goal: 100_000_000,
+            donor_balance: 200_000_000,
+            first_donation: 0,
+            milestones: std::vec::Vec::new(),
+            fee_bps: None,
+            reward_tiers: false,
+            other_token_rate: None,
+            match_pool: false,
+            limits: None,
+        }
+    }
+}
+
+fn setup_campaign<'a>(env: &Env, options: CampaignOptions) -> CampaignSetup<'a> {
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(env, &contract_id);
+
+    let owner = Address::generate(env);
+    let treasury = Address::generate(env);
+    let sponsor = Address::generate(env);
+    let deadline = env.ledger().timestamp() + 100;
+
+    let token_admin = Address::generate(env);
+    let token = create_token_contract(env, &token_admin);
+    let donors: std::vec::Vec<Address> = (0..3).map(|_| Address::generate(env)).collect();
+    for donor in donors.iter() {
+        token.mint(donor, &options.donor_balance);
+    }
+
+    let milestones = Vec::from_slice(env, &options.milestones);
+    let campaign_id = match options.fee_bps {
+        Some(fee_bps) => {
+            let platform_fee = PlatformFee { treasury: treasury.clone(), fee_bps };
+            client.create_campaign_with_fee(
+                &owner, &options.goal, &deadline, &token.address, &milestones, &platform_fee,
+            )
+        }
+        None => client.create_campaign(&owner, &options.goal, &deadline, &token.address, &milestones),
+    };
+
+    if options.reward_tiers {
+        let perk = BytesN::from_array(env, &[7u8; 32]);
+        assert_eq!(client.add_reward_tier(&campaign_id, &owner, &100_000_000, &1, &perk), 0);
+        assert_eq!(client.add_reward_tier(&campaign_id, &owner, &10_000_000, &5, &perk), 1);
+    }
+
+    let other_token = options.other_token_rate.map(|rate| {
+        let other = create_token_contract(env, &token_admin);
+        for donor in donors.iter() {
+            other.mint(donor, &options.donor_balance);
+        }
+        client.add_accepted_token(&campaign_id, &owner, &other.address, &Pricing::Fixed(rate));
+        token::Client::new(env, &other.address)
+    });
+
+    if options.match_pool {
+        token.mint(&sponsor, &100_000_000);
+        client.add_match_pool(&campaign_id, &sponsor, &50_000_000, &10_000, &30_000_000);
+    }
+
+    if let Some(limits) = options.limits {
+        client.set_donation_limits(&campaign_id, &owner, &limits);
+    }
+
+    if options.first_donation > 0 {
+        client.donate(&campaign_id, &donors[0], &options.first_donation);
+    }
+
+    CampaignSetup { client, token, other_token, owner, treasury, sponsor, donors, campaign_id, deadline }
+}
+
+// Test 27: Owner withdraws everything after a successful campaign
+#[test]
+fn test_withdraw_success() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, token: token_client, owner, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { first_donation: 120_000_000, ..Default::default() });
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+
+    assert_eq!(client.withdraw(&campaign_id, &owner), 120_000_000);
+
+    // The token's transfer event comes first
+    let event = Withdrawn { campaign_id, owner: owner.clone(), amount: 120_000_000 };
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![&env, (client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+
+    assert_eq!(client.get_withdrawn(&campaign_id), 120_000_000);
+    assert_eq!(token_client.balance(&owner), 120_000_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+// Test 28: Cannot withdraw before the deadline
+#[test]
+#[should_panic(expected = "Campaign belum berakhir")]
+fn test_withdraw_before_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { first_donation: 100_000_000, ..Default::default() });
+
+    // Should panic
+    client.withdraw(&campaign_id, &owner);
+}
+
+// Test 29: Cannot withdraw twice
+#[test]
+#[should_panic(expected = "Funds already withdrawn")]
+fn test_withdraw_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { first_donation: 100_000_000, ..Default::default() });
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+
+    client.withdraw(&campaign_id, &owner);
+
+    // Should panic
+    client.withdraw(&campaign_id, &owner);
+}
+
+// Test 30: A failed campaign cannot be withdrawn, donors refund instead
+#[test]
+fn test_withdraw_failed_campaign() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, token: token_client, owner, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { first_donation: 40_000_000, ..Default::default() });
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+
+    let result = client.try_withdraw(&campaign_id, &owner);
+    assert!(result.is_err());
+    assert_eq!(client.get_withdrawn(&campaign_id), 0);
+    assert_eq!(token_client.balance(&client.address), 40_000_000);
+}
+
+// Test 31: Only the owner can withdraw
+#[test]
+#[should_panic(expected = "Only the campaign owner can do this")]
+fn test_withdraw_not_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { first_donation: 100_000_000, ..Default::default() });
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+
+    // Should panic
+    client.withdraw(&campaign_id, &Address::generate(&env));
+}
+
+// Test 32: Donors claim reward tiers when donating
+#[test]
+fn test_donate_with_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { goal: 500_000_000, reward_tiers: true, ..Default::default() });
+
+    client.donate_with_tier(&campaign_id, &donors[0], &100_000_000, &0);
+    client.donate_with_tier(&campaign_id, &donors[1], &10_000_000, &1);
+
+    assert_eq!(client.get_donor_tier(&campaign_id, &donors[0]), Some(0));
+    assert_eq!(client.get_donor_tier(&campaign_id, &donors[1]), Some(1));
+    assert_eq!(client.get_donor_tier(&campaign_id, &donors[2]), None);
+    assert_eq!(client.get_total_raised(&campaign_id), 110_000_000);
+
+    assert_eq!(client.get_tier_remaining(&campaign_id, &0), 0);
+    assert_eq!(client.get_tier_remaining(&campaign_id, &1), 4);
+    assert_eq!(client.get_available_tiers(&campaign_id), vec![&env, 1]);
+    assert_eq!(client.get_tier_holders(&campaign_id, &0), vec![&env, donors[0].clone()]);
+
+    let tiers = client.get_reward_tiers(&campaign_id);
+    assert_eq!(tiers.len(), 2);
+    assert_eq!(tiers.get(1).unwrap().claimed, 1);
+}
+
+// Test 33: Earlier donations count towards a tier upgrade
+#[test]
+fn test_upgrade_tier_with_cumulative_donation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { goal: 500_000_000, reward_tiers: true, ..Default::default() });
+
+    client.donate_with_tier(&campaign_id, &donors[0], &60_000_000, &1);
+    client.donate_with_tier(&campaign_id, &donors[0], &40_000_000, &0);
+
+    // Moved to tier 0 and gave the tier 1 slot back
+    assert_eq!(client.get_donor_tier(&campaign_id, &donors[0]), Some(0));
+    assert_eq!(client.get_tier_remaining(&campaign_id, &1), 5);
+    assert_eq!(client.get_tier_remaining(&campaign_id, &0), 0);
+    assert_eq!(client.get_tier_holders(&campaign_id, &0), vec![&env, donors[0].clone()]);
+    assert_eq!(client.get_tier_holders(&campaign_id, &1).len(), 0);
+}
+
+// Test 34: Tier minimum is enforced
+#[test]
+#[should_panic(expected = "Donation below tier minimum")]
+fn test_tier_below_minimum() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { goal: 500_000_000, reward_tiers: true, ..Default::default() });
+
+    // Should panic
+    client.donate_with_tier(&campaign_id, &donors[0], &99_999_999, &0);
+}
+
+// Test 35: A full tier cannot be claimed
+#[test]
+#[should_panic(expected = "Tier sold out")]
+fn test_tier_sold_out() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { goal: 500_000_000, reward_tiers: true, ..Default::default() });
+    client.donate_with_tier(&campaign_id, &donors[0], &100_000_000, &0);
+
+    // Should panic
+    client.donate_with_tier(&campaign_id, &donors[1], &100_000_000, &0);
+}
+
+// Test 36: Refunds release the tier slot
+#[test]
+fn test_refund_releases_tier_slot() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { goal: 500_000_000, reward_tiers: true, ..Default::default() });
+    client.donate_with_tier(&campaign_id, &donors[0], &100_000_000, &0);
+    assert_eq!(client.get_tier_remaining(&campaign_id, &0), 0);
+
+    // Goal of 50 XLM is missed
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    client.refund(&campaign_id, &donors[0]);
+
+    assert_eq!(client.get_donor_tier(&campaign_id, &donors[0]), None);
+    assert_eq!(client.get_tier_remaining(&campaign_id, &0), 1);
+    assert_eq!(client.get_tier_holders(&campaign_id, &0).len(), 0);
+}
+
+// Backer-only contract that checks receipts through the DonorReceipts client
+#[contract]
+pub struct BackerGate;
+
+#[contractimpl]
+impl BackerGate {
+    pub fn can_enter(env: Env, crowdfunding: Address, campaign_id: u32, who: Address) -> bool {
+        DonorReceiptsClient::new(&env, &crowdfunding).has_backed(&campaign_id, &who)
+    }
+}
+
+// Test 37: Donations issue a soulbound receipt that tracks the total donated
+#[test]
+fn test_donation_issues_receipt() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, .. } = setup_campaign(&env, CampaignOptions::default());
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 10;
+    });
+    client.donate(&campaign_id, &donors[0], &30_000_000);
+    client.donate(&campaign_id, &donors[0], &20_000_000);
+
+    let receipts = client.receipts_of(&donors[0]);
+    assert_eq!(
+        receipts,
+        vec![&env, Receipt { campaign_id, amount: 50_000_000, issued_at: 10 }]
+    );
+    assert!(client.has_backed(&campaign_id, &donors[0]));
+    assert!(!client.has_backed(&campaign_id, &donors[1]));
+    assert_eq!(client.receipts_of(&donors[1]).len(), 0);
+}
+
+// Test 38: Other contracts can check whether an address backed a campaign
+#[test]
+fn test_other_contract_queries_receipts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, .. } = setup_campaign(&env, CampaignOptions::default());
+    let gate = BackerGateClient::new(&env, &env.register(BackerGate, ()));
+
+    client.donate(&campaign_id, &donors[0], &10_000_000);
+
+    assert!(gate.can_enter(&client.address, &campaign_id, &donors[0]));
+    assert!(!gate.can_enter(&client.address, &campaign_id, &donors[1]));
+}
+
+// Test 39: Refunds burn the receipt
+#[test]
+fn test_refund_burns_receipt() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions::default());
+    client.donate(&campaign_id, &donors[0], &10_000_000);
+    assert!(client.has_backed(&campaign_id, &donors[0]));
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    client.refund(&campaign_id, &donors[0]);
+
+    assert!(!client.has_backed(&campaign_id, &donors[0]));
+    assert_eq!(client.receipts_of(&donors[0]).len(), 0);
+}
+
+// Price oracle stand-in with a settable price
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn set_price(env: Env, price: i128) {
+        env.storage().instance().set(&symbol_short!("price"), &price);
+    }
+
+    pub fn price(env: Env, _asset: Address, _base: Address) -> i128 {
+        env.storage().instance().get(&symbol_short!("price")).unwrap()
+    }
+}
+
+// Test 40: Whitelisted tokens count towards the goal at their fixed rate
+#[test]
+fn test_donate_token_fixed_rate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, token: base, other_token, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { other_token_rate: Some(2 * RATE_PRECISION), ..Default::default() });
+    let other = other_token.unwrap();
+    let donor = donors[0].clone();
+
+    client.donate(&campaign_id, &donor, &20_000_000);
+    client.donate_token(&campaign_id, &donor, &other.address, &15_000_000);
+
+    assert_eq!(client.get_accepted_tokens(&campaign_id), vec![&env, base.address.clone(), other.address.clone()]);
+    assert_eq!(client.get_token_donation(&campaign_id, &donor, &base.address), 20_000_000);
+    assert_eq!(client.get_token_donation(&campaign_id, &donor, &other.address), 15_000_000);
+    assert_eq!(client.get_token_total(&campaign_id, &other.address), 15_000_000);
+    assert_eq!(client.get_donation(&campaign_id, &donor), 50_000_000);
+    assert_eq!(client.get_total_raised(&campaign_id), 50_000_000);
+    assert_eq!(client.get_progress_percentage(&campaign_id).goal_percentage, 50);
+    assert_eq!(other.balance(&client.address), 15_000_000);
+}
+
+// Test 41: Oracle-priced tokens are valued at the price when donated
+#[test]
+fn test_donate_token_oracle_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, other_token, owner, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { other_token_rate: Some(2 * RATE_PRECISION), ..Default::default() });
+    let other = other_token.unwrap();
+    let donor = donors[0].clone();
+    let oracle = MockOracleClient::new(&env, &env.register(MockOracle, ()));
+    oracle.set_price(&(RATE_PRECISION / 2));
+    client.add_accepted_token(&campaign_id, &owner, &other.address, &Pricing::Oracle(oracle.address.clone()));
+
+    assert_eq!(client.quote_donation(&campaign_id, &other.address, &10_000_000), 5_000_000);
+    client.donate_token(&campaign_id, &donor, &other.address, &10_000_000);
+
+    oracle.set_price(&(3 * RATE_PRECISION));
+    client.donate_token(&campaign_id, &donor, &other.address, &10_000_000);
+
+    assert_eq!(client.get_total_raised(&campaign_id), 35_000_000);
+    assert_eq!(client.get_token_total(&campaign_id, &other.address), 20_000_000);
+}
+
+// Test 42: Refunds return every token in kind
+#[test]
+fn test_multi_asset_refund() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, token: base, other_token, donors, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { other_token_rate: Some(2 * RATE_PRECISION), ..Default::default() });
+    let other = other_token.unwrap();
+    let donor = donors[0].clone();
+    client.donate(&campaign_id, &donor, &10_000_000);
+    client.donate_token(&campaign_id, &donor, &other.address, &5_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    assert_eq!(client.refund(&campaign_id, &donor), 20_000_000);
+
+    assert_eq!(base.balance(&donor), 200_000_000);
+    assert_eq!(other.balance(&donor), 200_000_000);
+    assert_eq!(client.get_token_donation(&campaign_id, &donor, &other.address), 0);
+    assert_eq!(client.get_token_total(&campaign_id, &other.address), 0);
+    assert_eq!(client.get_total_raised(&campaign_id), 0);
+}
+
+// Test 43: Withdrawal pays the owner in every token donated
+#[test]
+fn test_multi_asset_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, token: base, other_token, owner, donors, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { other_token_rate: Some(2 * RATE_PRECISION), ..Default::default() });
+    let other = other_token.unwrap();
+    let donor = donors[0].clone();
+    client.donate(&campaign_id, &donor, &40_000_000);
+    client.donate_token(&campaign_id, &donor, &other.address, &30_000_000);
+    assert!(client.is_goal_reached(&campaign_id));
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    assert_eq!(client.withdraw(&campaign_id, &owner), 100_000_000);
+
+    assert_eq!(base.balance(&owner), 40_000_000);
+    assert_eq!(other.balance(&owner), 30_000_000);
+    assert_eq!(other.balance(&client.address), 0);
+}
+
+// Test 44: Tokens outside the whitelist are rejected
+#[test]
+#[should_panic(expected = "Token not accepted")]
+fn test_donate_token_not_accepted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { other_token_rate: Some(2 * RATE_PRECISION), ..Default::default() });
+    let donor = donors[0].clone();
+    let token_admin = Address::generate(&env);
+    let stranger = create_token_contract(&env, &token_admin);
+    stranger.mint(&donor, &10_000_000);
+
+    // Should panic
+    client.donate_token(&campaign_id, &donor, &stranger.address, &10_000_000);
+}
+
+// Test 45: A token cannot be re-priced once it has been donated
+#[test]
+#[should_panic(expected = "Token already has donations")]
+fn test_reprice_donated_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, other_token, owner, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { other_token_rate: Some(2 * RATE_PRECISION), ..Default::default() });
+    let other = other_token.unwrap();
+    client.donate_token(&campaign_id, &donors[0], &other.address, &10_000_000);
+
+    // Should panic
+    client.add_accepted_token(&campaign_id, &owner, &other.address, &Pricing::Fixed(10 * RATE_PRECISION));
+}
+
+// Test 46: A cancelled campaign cannot whitelist tokens
+#[test]
+#[should_panic(expected = "Campaign has been cancelled")]
+fn test_add_accepted_token_cancelled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { other_token_rate: Some(2 * RATE_PRECISION), ..Default::default() });
+    client.cancel_campaign(&campaign_id, &owner, &BytesN::from_array(&env, &[9u8; 32]));
+
+    let token_admin = Address::generate(&env);
+    let stranger = create_token_contract(&env, &token_admin);
+
+    // Should panic
+    client.add_accepted_token(&campaign_id, &owner, &stranger.address, &Pricing::Fixed(RATE_PRECISION));
+}
+
+// Test 47: Cancelling lets donors refund in full before the deadline
+#[test]
+fn test_cancel_campaign_refunds_immediately() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, token: base, other_token, owner, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { other_token_rate: Some(2 * RATE_PRECISION), ..Default::default() });
+    let other = other_token.unwrap();
+    let donor = donors[0].clone();
+    client.donate(&campaign_id, &donor, &60_000_000);
+    client.donate_token(&campaign_id, &donor, &other.address, &30_000_000);
+    assert!(client.is_goal_reached(&campaign_id));
+
+    let reason = BytesN::from_array(&env, &[9u8; 32]);
+    client.cancel_campaign(&campaign_id, &owner, &reason);
+
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                Cancelled { campaign_id, owner: owner.clone(), reason_hash: reason.clone() }.topics(&env),
+                Cancelled { campaign_id, owner: owner.clone(), reason_hash: reason.clone() }.data(&env),
+            ),
+        ]
+    );
+    assert!(client.is_cancelled(&campaign_id));
+    assert_eq!(client.get_cancel_reason(&campaign_id), Some(reason));
+
+    assert_eq!(client.refund(&campaign_id, &donor), 120_000_000);
+    assert_eq!(base.balance(&donor), 200_000_000);
+    assert_eq!(other.balance(&donor), 200_000_000);
+    assert_eq!(client.get_total_raised(&campaign_id), 0);
+}
+
+// Test 48: A cancelled campaign takes no more donations
+#[test]
+#[should_panic(expected = "Campaign has been cancelled")]
+fn test_donate_to_cancelled_campaign() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { other_token_rate: Some(2 * RATE_PRECISION), ..Default::default() });
+    let donor = donors[0].clone();
+    client.cancel_campaign(&campaign_id, &owner, &BytesN::from_array(&env, &[9u8; 32]));
+
+    // Should panic
+    client.donate(&campaign_id, &donor, &10_000_000);
+}
+
+// Test 49: The owner cannot withdraw from a cancelled campaign
+#[test]
+#[should_panic(expected = "Campaign has been cancelled")]
+fn test_withdraw_cancelled_campaign() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { first_donation: 120_000_000, ..Default::default() });
+    client.cancel_campaign(&campaign_id, &owner, &BytesN::from_array(&env, &[9u8; 32]));
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+
+    // Should panic
+    client.withdraw(&campaign_id, &owner);
+}
+
+// Test 50: Only the owner can cancel
+#[test]
+#[should_panic(expected = "Only the campaign owner can do this")]
+fn test_cancel_campaign_not_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { other_token_rate: Some(2 * RATE_PRECISION), ..Default::default() });
+    let donor = donors[0].clone();
+
+    // Should panic
+    client.cancel_campaign(&campaign_id, &donor, &BytesN::from_array(&env, &[9u8; 32]));
+}
+
+// Test 51: A campaign that already paid out cannot be cancelled
+#[test]
+#[should_panic(expected = "Campaign funds already paid out")]
+fn test_cancel_campaign_after_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { first_donation: 120_000_000, ..Default::default() });
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    client.withdraw(&campaign_id, &owner);
+
+    // Should panic
+    client.cancel_campaign(&campaign_id, &owner, &BytesN::from_array(&env, &[9u8; 32]));
+}
+
+// Test 52: A majority of raised funds applies the extension
+#[test]
+fn test_extension_applies_with_majority() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, donors, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { goal: 500_000_000, ..Default::default() });
+    client.donate(&campaign_id, &donors[0], &40_000_000);
+    client.donate(&campaign_id, &donors[1], &35_000_000);
+    client.donate(&campaign_id, &donors[2], &25_000_000);
+
+    client.propose_extension(&campaign_id, &owner, &(deadline + 500));
+
+    // 40 of 100 is not a majority yet
+    client.vote_extension(&campaign_id, &donors[0], &true);
+    client.vote_extension(&campaign_id, &donors[1], &false);
+    assert_eq!(client.get_deadline(&campaign_id), deadline);
+
+    client.vote_extension(&campaign_id, &donors[2], &true);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ExtensionApplied { campaign_id, old_deadline: deadline, new_deadline: deadline + 500 }.topics(&env),
+                ExtensionApplied { campaign_id, old_deadline: deadline, new_deadline: deadline + 500 }.data(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_deadline(&campaign_id), deadline + 500);
+    assert_eq!(
+        client.get_extension(&campaign_id),
+        Some(Extension { new_deadline: deadline + 500, votes_for: 65_000_000, votes_against: 35_000_000, applied: true })
+    );
+    assert_eq!(client.get_extension_vote(&campaign_id, &donors[1]), Some(false));
+}
+
+// Test 53: Votes after the original deadline are too late
+#[test]
+#[should_panic(expected = "Campaign has ended")]
+fn test_extension_vote_after_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, donors, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { goal: 500_000_000, ..Default::default() });
+    client.donate(&campaign_id, &donors[0], &40_000_000);
+    client.propose_extension(&campaign_id, &owner, &(deadline + 500));
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+
+    // Should panic
+    client.vote_extension(&campaign_id, &donors[0], &true);
+}
+
+// Test 54: Donors who voted against can refund as soon as the extension applies
+#[test]
+fn test_extension_dissenter_refunds_immediately() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, donors, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { goal: 500_000_000, ..Default::default() });
+    client.donate(&campaign_id, &donors[0], &60_000_000);
+    client.donate(&campaign_id, &donors[1], &40_000_000);
+
+    client.propose_extension(&campaign_id, &owner, &(deadline + 500));
+    client.vote_extension(&campaign_id, &donors[1], &false);
+    client.vote_extension(&campaign_id, &donors[0], &true);
+
+    assert_eq!(client.refund(&campaign_id, &donors[1]), 40_000_000);
+    assert_eq!(client.get_total_raised(&campaign_id), 60_000_000);
+    assert!(client.try_refund(&campaign_id, &donors[0]).is_err());
+}
+
+// Test 55: Only donors get a vote
+#[test]
+#[should_panic(expected = "Only donors can vote")]
+fn test_extension_vote_non_donor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, donors, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { goal: 500_000_000, ..Default::default() });
+    client.donate(&campaign_id, &donors[0], &40_000_000);
+    client.propose_extension(&campaign_id, &owner, &(deadline + 500));
+
+    // Should panic
+    client.vote_extension(&campaign_id, &donors[1], &true);
+}
+
+// Test 56: Each donor votes once
+#[test]
+#[should_panic(expected = "Already voted")]
+fn test_extension_vote_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, donors, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { goal: 500_000_000, ..Default::default() });
+    client.donate(&campaign_id, &donors[0], &40_000_000);
+    client.donate(&campaign_id, &donors[1], &40_000_000);
+    client.propose_extension(&campaign_id, &owner, &(deadline + 500));
+    client.vote_extension(&campaign_id, &donors[0], &false);
+
+    // Should panic
+    client.vote_extension(&campaign_id, &donors[0], &true);
+}
+
+// Test 57: The platform fee is taken on withdrawal
+#[test]
+fn test_withdraw_pays_platform_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, token, owner, treasury, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { fee_bps: Some(250), ..Default::default() });
+    let donor = Address::generate(&env);
+    token.mint(&donor, &120_000_000);
+    client.donate(&campaign_id, &donor, &120_000_000);
+
+    assert_eq!(
+        client.get_platform_fee_config(&campaign_id),
+        Some(PlatformFee { treasury: treasury.clone(), fee_bps: 250 })
+    );
+    assert_eq!(client.get_gross_raised(&campaign_id), 120_000_000);
+    assert_eq!(client.get_platform_fee(&campaign_id), 3_000_000);
+    assert_eq!(client.get_net_payable(&campaign_id), 117_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    assert_eq!(client.withdraw(&campaign_id, &owner), 117_000_000);
+
+    let token_client = token::Client::new(&env, &token.address);
+    assert_eq!(token_client.balance(&owner), 117_000_000);
+    assert_eq!(token_client.balance(&treasury), 3_000_000);
+    assert_eq!(client.get_withdrawn(&campaign_id), 120_000_000);
+}
+
+// Test 58: Refunds are never charged
+#[test]
+fn test_refund_skips_platform_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, token, treasury, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { fee_bps: Some(250), ..Default::default() });
+    let donor = Address::generate(&env);
+    token.mint(&donor, &40_000_000);
+    client.donate(&campaign_id, &donor, &40_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    assert_eq!(client.refund(&campaign_id, &donor), 40_000_000);
+
+    let token_client = token::Client::new(&env, &token.address);
+    assert_eq!(token_client.balance(&donor), 40_000_000);
+    assert_eq!(token_client.balance(&treasury), 0);
+}
+
+// Test 59: Each milestone payout carries its share of the fee
+#[test]
+fn test_release_milestone_pays_platform_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let milestones = std::vec![milestone(&env, 1, 4_000), milestone(&env, 2, 6_000)];
+    let CampaignSetup { client, token, owner, treasury, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { fee_bps: Some(1_000), milestones, ..Default::default() });
+    let donor = Address::generate(&env);
+    token.mint(&donor, &100_000_000);
+    client.donate(&campaign_id, &donor, &100_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    assert_eq!(client.release_milestone(&campaign_id, &owner), 36_000_000);
+    client.vote_milestone(&campaign_id, &donor, &true);
+    assert_eq!(client.release_milestone(&campaign_id, &owner), 54_000_000);
+
+    let token_client = token::Client::new(&env, &token.address);
+    assert_eq!(token_client.balance(&owner), 90_000_000);
+    assert_eq!(token_client.balance(&treasury), 10_000_000);
+}
+
+// Test 60: The platform fee is capped
+#[test]
+#[should_panic(expected = "Platform fee too high")]
+fn test_platform_fee_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // Should panic
+    setup_campaign(&env, CampaignOptions { fee_bps: Some(MAX_PLATFORM_FEE_BPS + 1), ..Default::default() });
+}
+
+// Test 61: Donations draw matched funds up to the per-donor cap
+#[test]
+fn test_donations_draw_match() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, sponsor, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { match_pool: true, ..Default::default() });
+    client.donate(&campaign_id, &donors[0], &20_000_000);
+    client.donate(&campaign_id, &donors[0], &20_000_000);
+    client.donate(&campaign_id, &donors[1], &10_000_000);
+
+    assert_eq!(client.get_matched(&campaign_id, &donors[0]), 30_000_000);
+    assert_eq!(client.get_matched(&campaign_id, &donors[1]), 10_000_000);
+    assert_eq!(client.get_donation(&campaign_id, &donors[0]), 40_000_000);
+    assert_eq!(client.get_total_raised(&campaign_id), 90_000_000);
+    assert_eq!(
+        client.get_match_pool(&campaign_id),
+        Some(MatchPool {
+            sponsor,
+            available: 10_000_000,
+            matched: 40_000_000,
+            ratio: 10_000,
+            cap_per_donor: 30_000_000,
+        })
+    );
+}
+
+// Test 62: Refunds put the matched portion back in the pool
+#[test]
+fn test_refund_reverses_match() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, token: token_client, sponsor, donors, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { match_pool: true, ..Default::default() });
+    client.donate(&campaign_id, &donors[0], &20_000_000);
+    assert_eq!(client.get_total_raised(&campaign_id), 40_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    assert_eq!(client.refund(&campaign_id, &donors[0]), 20_000_000);
+
+    assert_eq!(client.get_total_raised(&campaign_id), 0);
+    assert_eq!(client.get_matched(&campaign_id, &donors[0]), 0);
+    assert_eq!(client.get_match_pool(&campaign_id).unwrap().available, 50_000_000);
+
+    assert_eq!(client.reclaim_match_pool(&campaign_id, &sponsor), 50_000_000);
+    assert_eq!(token_client.balance(&sponsor), 100_000_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+// Test 63: Unused pool funds return to the sponsor after a successful campaign
+#[test]
+fn test_reclaim_unused_match_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, token: token_client, owner, sponsor, donors, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { match_pool: true, ..Default::default() });
+    client.donate(&campaign_id, &donors[0], &60_000_000);
+    client.donate(&campaign_id, &donors[1], &10_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    assert_eq!(client.withdraw(&campaign_id, &owner), 110_000_000);
+    assert_eq!(client.reclaim_match_pool(&campaign_id, &sponsor), 10_000_000);
+
+    assert_eq!(token_client.balance(&owner), 110_000_000);
+    assert_eq!(token_client.balance(&sponsor), 60_000_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+// Test 64: The pool stays locked while the campaign runs
+#[test]
+#[should_panic(expected = "Campaign belum berakhir")]
+fn test_reclaim_match_pool_before_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, sponsor, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { match_pool: true, ..Default::default() });
+
+    // Should panic
+    client.reclaim_match_pool(&campaign_id, &sponsor);
+}
+
+// Test 65: Matched funds carry no vote, so donors alone decide an extension
+#[test]
+fn test_extension_majority_ignores_matched_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, donors, campaign_id, deadline, .. } =
+        setup_campaign(&env, CampaignOptions { match_pool: true, ..Default::default() });
+    client.donate(&campaign_id, &donors[0], &40_000_000);
+    client.donate(&campaign_id, &donors[1], &10_000_000);
+    assert_eq!(client.get_total_raised(&campaign_id), 90_000_000);
+
+    client.propose_extension(&campaign_id, &owner, &(deadline + 500));
+
+    // 40 of the 50 donors gave is a majority, even though 40M more came from the pool
+    client.vote_extension(&campaign_id, &donors[0], &true);
+    assert!(client.get_extension(&campaign_id).unwrap().applied);
+    assert_eq!(client.get_deadline(&campaign_id), deadline + 500);
+}
+
+// 5M minimum, 60M per donor and a 150M hard cap
+fn hard_cap_limits() -> DonationLimits {
+    DonationLimits {
+        min_donation: Some(5_000_000),
+        max_per_donor: Some(60_000_000),
+        hard_cap: Some(150_000_000),
+    }
+}
+
+// Test 66: Donations past the hard cap are only partially accepted
+#[test]
+fn test_hard_cap_partial_acceptance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, token: token_client, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { limits: Some(hard_cap_limits()), ..Default::default() });
+    assert_eq!(client.get_donation_limits(&campaign_id).hard_cap, Some(150_000_000));
+
+    client.donate(&campaign_id, &donors[0], &60_000_000);
+    client.donate(&campaign_id, &donors[1], &60_000_000);
+    assert_eq!(client.get_remaining_capacity(&campaign_id), Some(30_000_000));
+    assert_eq!(
+        client.get_progress_percentage(&campaign_id),
+        Progress { goal_percentage: 120, cap_percentage: Some(80) }
+    );
+
+    client.donate(&campaign_id, &donors[2], &50_000_000);
+
+    assert_eq!(client.get_donation(&campaign_id, &donors[2]), 30_000_000);
+    assert_eq!(token_client.balance(&donors[2]), 170_000_000);
+    assert_eq!(client.get_total_raised(&campaign_id), 150_000_000);
+    assert_eq!(client.get_remaining_capacity(&campaign_id), Some(0));
+    assert_eq!(
+        client.get_progress_percentage(&campaign_id),
+        Progress { goal_percentage: 150, cap_percentage: Some(100) }
+    );
+}
+
+// Test 67: A full campaign rejects further donations
+#[test]
+#[should_panic(expected = "Hard cap reached")]
+fn test_donate_after_hard_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { limits: Some(hard_cap_limits()), ..Default::default() });
+    client.donate(&campaign_id, &donors[0], &60_000_000);
+    client.donate(&campaign_id, &donors[1], &60_000_000);
+    client.donate(&campaign_id, &donors[2], &30_000_000);
+
+    // Should panic
+    client.donate(&campaign_id, &donors[2], &10_000_000);
+}
+
+// Test 68: Donations below the minimum are rejected
+#[test]
+#[should_panic(expected = "Donation below campaign minimum")]
+fn test_donate_below_minimum() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { limits: Some(hard_cap_limits()), ..Default::default() });
+
+    // Should panic
+    client.donate(&campaign_id, &donors[0], &4_000_000);
+}
+
+// Test 69: A donor cannot give more than the per-donor maximum in total
+#[test]
+#[should_panic(expected = "Donation above per-donor maximum")]
+fn test_donate_above_per_donor_maximum() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { limits: Some(hard_cap_limits()), ..Default::default() });
+    client.donate(&campaign_id, &donors[0], &40_000_000);
+
+    // Should panic
+    client.donate(&campaign_id, &donors[0], &30_000_000);
+}
+
+// Test 70: Limits are fixed once donations come in
+#[test]
+#[should_panic(expected = "Limits must be set before the first donation")]
+fn test_set_limits_after_donation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions { limits: Some(hard_cap_limits()), ..Default::default() });
+    client.donate(&campaign_id, &donors[0], &10_000_000);
+
+    // Should panic
+    client.set_donation_limits(&campaign_id, &owner, &DonationLimits::default());
+}
+
+// Merkle leaf for an allowlisted donor
+fn allowlist_leaf(env: &Env, donor: &Address) -> BytesN<32> {
+    env.crypto().sha256(&donor.clone().to_xdr(env)).into()
+}
+
+// Parent node of two Merkle nodes, hashed in sorted order
+fn allowlist_node(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a < b { (a, b) } else { (b, a) };
+    let mut pair = Bytes::from(first.clone());
+    pair.append(&Bytes::from(second.clone()));
+    env.crypto().sha256(&pair).into()
+}
+
+// Test 71: Allowlisted donors can give to a private campaign
+#[test]
+fn test_allowlisted_donor_can_donate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions::default());
+    assert!(!client.is_private(&campaign_id));
+
+    client.add_allowed_donors(&campaign_id, &owner, &vec![&env, donors[0].clone()]);
+    assert!(client.is_private(&campaign_id));
+    assert!(client.is_allowed(&campaign_id, &donors[0]));
+    assert!(!client.is_allowed(&campaign_id, &donors[1]));
+
+    client.donate(&campaign_id, &donors[0], &10_000_000);
+    assert_eq!(client.get_donation(&campaign_id, &donors[0]), 10_000_000);
+}
+
+// Test 72: Donors off the allowlist are rejected with a typed error
+#[test]
+fn test_non_allowlisted_donor_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions::default());
+    client.add_allowed_donors(&campaign_id, &owner, &vec![&env, donors[0].clone(), donors[1].clone()]);
+    client.remove_allowed_donor(&campaign_id, &owner, &donors[1]);
+
+    assert_eq!(
+        client.try_donate(&campaign_id, &donors[1], &10_000_000),
+        Err(Ok(AllowlistError::NotAllowlisted.into()))
+    );
+    assert_eq!(
+        client.try_donate_with_tier(&campaign_id, &donors[2], &10_000_000, &1),
+        Err(Ok(AllowlistError::NotAllowlisted.into()))
+    );
+    assert_eq!(client.get_total_raised(&campaign_id), 0);
+}
+
+// Test 73: An empty allowlist is rejected instead of closing the campaign
+#[test]
+#[should_panic(expected = "No donors to allow")]
+fn test_add_empty_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, campaign_id, .. } = setup_campaign(&env, CampaignOptions::default());
+
+    // Should panic
+    client.add_allowed_donors(&campaign_id, &owner, &Vec::new(&env));
+}
+
+// Test 74: Donors prove membership of a Merkle allowlist when donating
+#[test]
+fn test_donate_with_merkle_proof() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions::default());
+    let outsider = Address::generate(&env);
+
+    let leaves: std::vec::Vec<BytesN<32>> = [&donors[0], &donors[1], &donors[2], &outsider]
+        .iter()
+        .map(|donor| allowlist_leaf(&env, donor))
+        .collect();
+    let left = allowlist_node(&env, &leaves[0], &leaves[1]);
+    let right = allowlist_node(&env, &leaves[2], &leaves[3]);
+    let root = allowlist_node(&env, &left, &right);
+    client.set_allowlist_root(&campaign_id, &owner, &root);
+
+    assert!(!client.is_allowed(&campaign_id, &donors[2]));
+    client.donate_with_proof(&campaign_id, &donors[2], &10_000_000, &vec![&env, leaves[3].clone(), left]);
+
+    // The proof listed the donor, so plain donations work from now on
+    assert!(client.is_allowed(&campaign_id, &donors[2]));
+    client.donate(&campaign_id, &donors[2], &5_000_000);
+    assert_eq!(client.get_donation(&campaign_id, &donors[2]), 15_000_000);
+    assert_eq!(client.get_allowlist_root(&campaign_id), Some(root));
+}
+
+// Test 75: A proof for a different tree is rejected with a typed error
+#[test]
+fn test_donate_with_invalid_proof() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let CampaignSetup { client, owner, donors, campaign_id, .. } =
+        setup_campaign(&env, CampaignOptions::default());
+
+    let root = allowlist_node(&env, &allowlist_leaf(&env, &donors[0]), &allowlist_leaf(&env, &donors[1]));
+    client.set_allowlist_root(&campaign_id, &owner, &root);
+
+    let proof = vec![&env, allowlist_leaf(&env, &donors[0])];
+    assert_eq!(
+        client.try_donate_with_proof(&campaign_id, &donors[2], &10_000_000, &proof),
+        Err(Ok(AllowlistError::InvalidProof.into()))
+    );
+    assert!(!client.is_allowed(&campaign_id, &donors[2]));
+}
+
+// Test 76: Later milestones are only released once donors approve them
+#[test]
+fn test_release_milestone_waits_for_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let donor1 = Address::generate(&env);
+    let donor2 = Address::generate(&env);
+    let goal = 100_000_000i128;
+    let deadline = env.ledger().timestamp() + 100;
+
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    token.mint(&donor1, &50_000_000);
+    token.mint(&donor2, &50_000_000);
+
+    let milestones = vec![&env, milestone(&env, 1, 5_000), milestone(&env, 2, 5_000)];
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &milestones);
+    client.donate(&campaign_id, &donor1, &50_000_000);
+    client.donate(&campaign_id, &donor2, &50_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    assert_eq!(client.release_milestone(&campaign_id, &owner), 50_000_000);
+
+    // Half of the funds is not a majority
+    client.vote_milestone(&campaign_id, &donor1, &true);
+    assert!(!client.is_milestone_approved(&campaign_id, &1));
+    assert!(client.try_release_milestone(&campaign_id, &owner).is_err());
+    assert!(client.try_vote_milestone(&campaign_id, &donor1, &true).is_err());
+
+    client.vote_milestone(&campaign_id, &donor2, &true);
+    assert_eq!(
+        client.get_milestone_tally(&campaign_id, &1),
+        MilestoneTally { approvals: 100_000_000, rejections: 0 }
+    );
+    assert_eq!(client.get_milestone_vote(&campaign_id, &1, &donor2), Some(true));
+    assert_eq!(client.release_milestone(&campaign_id, &owner), 50_000_000);
+}
+
+// Test 77: Donors rejecting the next milestone abandon the campaign
+#[test]
+fn test_donors_reject_milestone_and_refund() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let donor1 = Address::generate(&env);
+    let donor2 = Address::generate(&env);
+    let goal = 100_000_000i128;
+    let deadline = env.ledger().timestamp() + 100;
+
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    token.mint(&donor1, &75_000_000);
+    token.mint(&donor2, &25_000_000);
+
+    let milestones = vec![&env, milestone(&env, 1, 4_000), milestone(&env, 2, 6_000)];
+    let campaign_id = client.create_campaign(&owner, &goal, &deadline, &token.address, &milestones);
+    client.donate(&campaign_id, &donor1, &75_000_000);
+    client.donate(&campaign_id, &donor2, &25_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    client.release_milestone(&campaign_id, &owner);
+
+    client.vote_milestone(&campaign_id, &donor1, &false);
+    let event = MilestoneRejected { campaign_id, milestone: 1 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert!(client.is_abandoned(&campaign_id));
+    assert!(client.try_release_milestone(&campaign_id, &owner).is_err());
+
+    // 60% of every donation comes back, as if the owner had abandoned
+    assert_eq!(client.refund(&campaign_id, &donor1), 45_000_000);
+    assert_eq!(client.refund(&campaign_id, &donor2), 15_000_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+// Test 78: The first milestone is paid without a vote, so donors cannot vote on it
+#[test]
+#[should_panic(expected = "The first milestone needs no approval")]
+fn test_vote_on_first_milestone() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundingContract, ());
+    let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let donor = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 100;
+
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    token.mint(&donor, &100_000_000);
+
+    let milestones = vec![&env, milestone(&env, 1, 5_000), milestone(&env, 2, 5_000)];
+    let campaign_id = client.create_campaign(&owner, &100_000_000, &deadline, &token.address, &milestones);
+    client.donate(&campaign_id, &donor, &100_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+
+    // Should panic
+    client.vote_milestone(&campaign_id, &donor, &false);
+}
@@ -0,0 +1,152 @@
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, Vec};
+
+use crate::{CrowdfundingContract, CrowdfundingContractArgs, CrowdfundingContractClient, DataKey};
+
+// A Kickstarter-style reward: donors who gave at least `min_amount` can
+// claim one of `quantity` slots
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardTier {
+    pub min_amount: i128,
+    pub quantity: u32,
+    pub claimed: u32,
+    pub perk_hash: BytesN<32>,
+}
+
+#[contractimpl]
+impl CrowdfundingContract {
+
+    // Owner adds a reward tier to a running campaign, returns the tier id
+    pub fn add_reward_tier(
+        env: Env,
+        campaign_id: u32,
+        owner: Address,
+        min_amount: i128,
+        quantity: u32,
+        perk_hash: BytesN<32>,
+    ) -> u32 {
+        owner.require_auth();
+
+        let campaign = Self::read_campaign(&env, campaign_id);
+        Self::require_owner(&campaign, &owner);
+
+        if env.ledger().timestamp() > campaign.deadline {
+            panic!("Campaign has ended");
+        }
+        if min_amount <= 0 {
+            panic!("Tier minimum must be positive");
+        }
+        if quantity == 0 {
+            panic!("Tier quantity must be positive");
+        }
+
+        let mut tiers = Self::get_reward_tiers(env.clone(), campaign_id);
+        tiers.push_back(RewardTier { min_amount, quantity, claimed: 0, perk_hash });
+        Self::write_tiers(&env, campaign_id, &tiers);
+
+        tiers.len() - 1
+    }
+
+    // Donate and claim a reward tier in one call. The donor's total donation
+    // must cover the tier minimum; picking a new tier gives up the old one.
+    pub fn donate_with_tier(env: Env, campaign_id: u32, donor: Address, amount: i128, tier_id: u32) {
+        donor.require_auth();
+
+        Self::accept_donation(&env, campaign_id, &donor, amount);
+
+        let mut tiers = Self::get_reward_tiers(env.clone(), campaign_id);
+        let mut tier = tiers.get(tier_id).unwrap_or_else(|| panic!("Tier not found"));
+        let current = Self::get_donor_tier(env.clone(), campaign_id, donor.clone());
+
+        if Self::get_donation(env.clone(), campaign_id, donor.clone()) < tier.min_amount {
+            panic!("Donation below tier minimum");
+        }
+        if current == Some(tier_id) {
+            return;
+        }
+        if tier.claimed >= tier.quantity {
+            panic!("Tier sold out");
+        }
+
+        if let Some(previous_id) = current {
+            let mut previous = tiers.get(previous_id).unwrap();
+            previous.claimed -= 1;
+            tiers.set(previous_id, previous);
+            Self::remove_tier_holder(&env, campaign_id, previous_id, &donor);
+        }
+        tier.claimed += 1;
+        tiers.set(tier_id, tier);
+        Self::write_tiers(&env, campaign_id, &tiers);
+
+        let mut holders = Self::get_tier_holders(env.clone(), campaign_id, tier_id);
+        holders.push_back(donor.clone());
+        Self::write_entry(&env, &DataKey::TierHolders(campaign_id, tier_id), &holders);
+        Self::write_entry(&env, &DataKey::DonorTier(campaign_id, donor), &tier_id);
+    }
+
+    pub fn get_reward_tiers(env: Env, campaign_id: u32) -> Vec<RewardTier> {
+        env.storage().persistent()
+            .get(&DataKey::RewardTiers(campaign_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // Slots still open in a tier, 0 for unknown tiers
+    pub fn get_tier_remaining(env: Env, campaign_id: u32, tier_id: u32) -> u32 {
+        match Self::get_reward_tiers(env, campaign_id).get(tier_id) {
+            Some(tier) => tier.quantity - tier.claimed,
+            None => 0,
+        }
+    }
+
+    // Ids of tiers that still have open slots
+    pub fn get_available_tiers(env: Env, campaign_id: u32) -> Vec<u32> {
+        let mut result = Vec::new(&env);
+        for (tier_id, tier) in Self::get_reward_tiers(env.clone(), campaign_id).iter().enumerate() {
+            if tier.claimed < tier.quantity {
+                result.push_back(tier_id as u32);
+            }
+        }
+        result
+    }
+
+    pub fn get_donor_tier(env: Env, campaign_id: u32, donor: Address) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::DonorTier(campaign_id, donor))
+    }
+
+    // Donors currently holding `tier_id`
+    pub fn get_tier_holders(env: Env, campaign_id: u32, tier_id: u32) -> Vec<Address> {
+        env.storage().persistent()
+            .get(&DataKey::TierHolders(campaign_id, tier_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // Give the donor's tier slot back, called when the donation is refunded
+    pub(crate) fn release_tier(env: &Env, campaign_id: u32, donor: &Address) {
+        let tier_id = match Self::get_donor_tier(env.clone(), campaign_id, donor.clone()) {
+            Some(tier_id) => tier_id,
+            None => return,
+        };
+
+        let mut tiers = Self::get_reward_tiers(env.clone(), campaign_id);
+        let mut tier = tiers.get(tier_id).unwrap();
+        tier.claimed -= 1;
+        tiers.set(tier_id, tier);
+        Self::write_tiers(env, campaign_id, &tiers);
+
+        Self::remove_tier_holder(env, campaign_id, tier_id, donor);
+        env.storage().persistent().remove(&DataKey::DonorTier(campaign_id, donor.clone()));
+    }
+
+    // A tier's holder list is bounded by its quantity, so it fits in one entry
+    fn remove_tier_holder(env: &Env, campaign_id: u32, tier_id: u32, donor: &Address) {
+        let mut holders = Self::get_tier_holders(env.clone(), campaign_id, tier_id);
+        if let Some(index) = holders.first_index_of(donor) {
+            holders.remove(index);
+            Self::write_entry(env, &DataKey::TierHolders(campaign_id, tier_id), &holders);
+        }
+    }
+
+    fn write_tiers(env: &Env, campaign_id: u32, tiers: &Vec<RewardTier>) {
+        Self::write_entry(env, &DataKey::RewardTiers(campaign_id), tiers);
+    }
+}
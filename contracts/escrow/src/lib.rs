@@ -0,0 +1,204 @@
+#![no_std]
+use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env, Symbol};
+
+// Storage keys
+const CLIENT: Symbol = symbol_short!("client");
+const FREELANCER: Symbol = symbol_short!("freelncr");
+const ARBITER: Symbol = symbol_short!("arbiter");
+const AMOUNT: Symbol = symbol_short!("amount");
+const TOKEN: Symbol = symbol_short!("token");
+const STATUS: Symbol = symbol_short!("status");
+const IS_ALREADY_INIT: Symbol = symbol_short!("is_init");
+
+// Escrow lifecycle
+pub const STATUS_CREATED: u32 = 0;
+pub const STATUS_IN_PROGRESS: u32 = 1;
+pub const STATUS_SUBMITTED: u32 = 2;
+pub const STATUS_APPROVED: u32 = 3;
+pub const STATUS_DISPUTED: u32 = 4;
+pub const STATUS_RESOLVED: u32 = 5;
+
+#[contract]
+pub struct EscrowContract;
+
+#[contractimpl]
+impl EscrowContract {
+
+    // Client locks `amount` of `token` for the freelancer, with an arbiter
+    // to settle disputes
+    pub fn create_escrow(
+        env: Env,
+        client: Address,
+        freelancer: Address,
+        arbiter: Address,
+        amount: i128,
+        token: Address,
+    ) {
+        client.require_auth();
+
+        let is_init: bool = env.storage().instance().get(&IS_ALREADY_INIT).unwrap_or(false);
+        if is_init {
+            panic!("Escrow already created");
+        }
+
+        if amount <= 0 {
+            panic!("Escrow amount must be positive");
+        }
+
+        let token_client = token::Client::new(&env, &token);
+        let contract_address = env.current_contract_address();
+        token_client.transfer(&client, &contract_address, &amount);
+
+        env.storage().instance().set(&CLIENT, &client);
+        env.storage().instance().set(&FREELANCER, &freelancer);
+        env.storage().instance().set(&ARBITER, &arbiter);
+        env.storage().instance().set(&AMOUNT, &amount);
+        env.storage().instance().set(&TOKEN, &token);
+        env.storage().instance().set(&STATUS, &STATUS_CREATED);
+        env.storage().instance().set(&IS_ALREADY_INIT, &true);
+    }
+
+    pub fn start_work(env: Env, freelancer: Address) {
+        freelancer.require_auth();
+
+        Self::require_initialized(&env);
+
+        let freelancer_address: Address = env.storage().instance().get(&FREELANCER).unwrap();
+        if freelancer != freelancer_address {
+            panic!("Only the freelancer can start work");
+        }
+        if Self::get_status(env.clone()) != STATUS_CREATED {
+            panic!("Work can only start on a new escrow");
+        }
+
+        env.storage().instance().set(&STATUS, &STATUS_IN_PROGRESS);
+    }
+
+    pub fn submit_work(env: Env, freelancer: Address) {
+        freelancer.require_auth();
+
+        Self::require_initialized(&env);
+
+        let freelancer_address: Address = env.storage().instance().get(&FREELANCER).unwrap();
+        if freelancer != freelancer_address {
+            panic!("Only the freelancer can submit work");
+        }
+        if Self::get_status(env.clone()) != STATUS_IN_PROGRESS {
+            panic!("Work has not been started");
+        }
+
+        env.storage().instance().set(&STATUS, &STATUS_SUBMITTED);
+    }
+
+    // Client accepts the submitted work and the freelancer gets paid in full
+    pub fn approve_work(env: Env, client: Address) {
+        client.require_auth();
+
+        Self::require_initialized(&env);
+
+        let client_address: Address = env.storage().instance().get(&CLIENT).unwrap();
+        if client != client_address {
+            panic!("Only the client can approve work");
+        }
+        if Self::get_status(env.clone()) != STATUS_SUBMITTED {
+            panic!("Work has not been submitted");
+        }
+
+        env.storage().instance().set(&STATUS, &STATUS_APPROVED);
+
+        let freelancer: Address = env.storage().instance().get(&FREELANCER).unwrap();
+        let amount: i128 = env.storage().instance().get(&AMOUNT).unwrap();
+        Self::pay(&env, &freelancer, amount);
+    }
+
+    // Client or freelancer freezes the escrow until the arbiter decides
+    pub fn dispute_escrow(env: Env, caller: Address) {
+        caller.require_auth();
+
+        Self::require_initialized(&env);
+
+        let client: Address = env.storage().instance().get(&CLIENT).unwrap();
+        let freelancer: Address = env.storage().instance().get(&FREELANCER).unwrap();
+        if caller != client && caller != freelancer {
+            panic!("Only the client or freelancer can dispute");
+        }
+
+        let status = Self::get_status(env.clone());
+        if status != STATUS_IN_PROGRESS && status != STATUS_SUBMITTED {
+            panic!("Escrow cannot be disputed in its current state");
+        }
+
+        env.storage().instance().set(&STATUS, &STATUS_DISPUTED);
+    }
+
+    // Arbiter pays `percentage` of the escrow to the freelancer and refunds
+    // the rest to the client
+    pub fn resolve_dispute(env: Env, arbiter: Address, percentage: i128) {
+        arbiter.require_auth();
+
+        Self::require_initialized(&env);
+
+        let arbiter_address: Address = env.storage().instance().get(&ARBITER).unwrap();
+        if arbiter != arbiter_address {
+            panic!("Only the arbiter can resolve disputes");
+        }
+        if Self::get_status(env.clone()) != STATUS_DISPUTED {
+            panic!("Escrow is not disputed");
+        }
+
+        if !(0..=100).contains(&percentage) {
+            panic!("Percentage must be between 0 and 100");
+        }
+
+        env.storage().instance().set(&STATUS, &STATUS_RESOLVED);
+
+        let client: Address = env.storage().instance().get(&CLIENT).unwrap();
+        let freelancer: Address = env.storage().instance().get(&FREELANCER).unwrap();
+        let amount: i128 = env.storage().instance().get(&AMOUNT).unwrap();
+
+        let to_freelancer = amount * percentage / 100;
+        let to_client = amount - to_freelancer;
+        if to_freelancer > 0 {
+            Self::pay(&env, &freelancer, to_freelancer);
+        }
+        if to_client > 0 {
+            Self::pay(&env, &client, to_client);
+        }
+    }
+
+    pub fn get_status(env: Env) -> u32 {
+        env.storage().instance().get(&STATUS).unwrap_or(STATUS_CREATED)
+    }
+
+    pub fn get_amount(env: Env) -> i128 {
+        env.storage().instance().get(&AMOUNT).unwrap_or(0)
+    }
+
+    pub fn get_client(env: Env) -> Option<Address> {
+        env.storage().instance().get(&CLIENT)
+    }
+
+    pub fn get_freelancer(env: Env) -> Option<Address> {
+        env.storage().instance().get(&FREELANCER)
+    }
+
+    pub fn get_arbiter(env: Env) -> Option<Address> {
+        env.storage().instance().get(&ARBITER)
+    }
+
+    fn pay(env: &Env, to: &Address, amount: i128) {
+        let token_address: Address = env.storage().instance().get(&TOKEN).unwrap();
+        let token_client = token::Client::new(env, &token_address);
+        token_client.transfer(&env.current_contract_address(), to, &amount);
+    }
+
+    fn require_initialized(env: &Env) {
+        let is_init: bool = env.storage().instance().get(&IS_ALREADY_INIT).unwrap_or(false);
+        if !is_init {
+            panic!("Escrow not created");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,350 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+
+// Helper function to create a mock token contract for testing
+fn create_token_contract<'a>(env: &Env, admin: &Address) -> token::StellarAssetClient<'a> {
+    let token_address = env.register_stellar_asset_contract_v2(admin.clone());
+    token::StellarAssetClient::new(env, &token_address.address())
+}
+
+// Escrow parties and a funded token, with the escrow not yet created
+struct EscrowSetup<'a> {
+    escrow: EscrowContractClient<'a>,
+    token: token::Client<'a>,
+    client: Address,
+    freelancer: Address,
+    arbiter: Address,
+}
+
+fn setup_escrow<'a>(env: &Env, client_balance: i128) -> EscrowSetup<'a> {
+    let contract_id = env.register(EscrowContract, ());
+    let escrow = EscrowContractClient::new(env, &contract_id);
+
+    let client = Address::generate(env);
+    let freelancer = Address::generate(env);
+    let arbiter = Address::generate(env);
+
+    let token_admin = Address::generate(env);
+    let token = create_token_contract(env, &token_admin);
+    if client_balance > 0 {
+        token.mint(&client, &client_balance);
+    }
+
+    EscrowSetup {
+        escrow,
+        token: token::Client::new(env, &token.address),
+        client,
+        freelancer,
+        arbiter,
+    }
+}
+
+// Escrow of 10 XLM with work started, ready to be disputed or submitted
+fn setup_started_escrow<'a>(env: &Env) -> EscrowSetup<'a> {
+    let s = setup_escrow(env, 100_000_000);
+    s.escrow.create_escrow(&s.client, &s.freelancer, &s.arbiter, &100_000_000, &s.token.address);
+    s.escrow.start_work(&s.freelancer);
+    s
+}
+
+// Test 1: Client creates a funded escrow
+#[test]
+fn test_create_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_escrow(&env, 100_000_000);
+    s.escrow.create_escrow(&s.client, &s.freelancer, &s.arbiter, &100_000_000, &s.token.address);
+
+    assert_eq!(s.escrow.get_status(), STATUS_CREATED);
+    assert_eq!(s.token.balance(&s.escrow.address), 100_000_000);
+    assert_eq!(s.token.balance(&s.client), 0);
+}
+
+// Test 2: Freelancer starts work
+#[test]
+fn test_start_work() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+
+    assert_eq!(s.escrow.get_status(), STATUS_IN_PROGRESS);
+}
+
+// Test 3: Freelancer submits work
+#[test]
+fn test_submit_work() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+    s.escrow.submit_work(&s.freelancer);
+
+    assert_eq!(s.escrow.get_status(), STATUS_SUBMITTED);
+}
+
+// Test 4: Client approves and the freelancer is paid in full
+#[test]
+fn test_approve_work_happy_path() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+    s.escrow.submit_work(&s.freelancer);
+    s.escrow.approve_work(&s.client);
+
+    assert_eq!(s.escrow.get_status(), STATUS_APPROVED);
+    assert_eq!(s.token.balance(&s.freelancer), 100_000_000);
+    assert_eq!(s.token.balance(&s.escrow.address), 0);
+}
+
+// Test 5: Client disputes the submitted work
+#[test]
+fn test_client_raises_dispute() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+    s.escrow.submit_work(&s.freelancer);
+    s.escrow.dispute_escrow(&s.client);
+
+    assert_eq!(s.escrow.get_status(), STATUS_DISPUTED);
+    assert_eq!(s.token.balance(&s.escrow.address), 100_000_000);
+}
+
+// Test 6: Freelancer disputes while work is in progress
+#[test]
+fn test_freelancer_raises_dispute() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+    s.escrow.dispute_escrow(&s.freelancer);
+
+    assert_eq!(s.escrow.get_status(), STATUS_DISPUTED);
+}
+
+// Test 7: Arbiter splits the escrow 50/50
+#[test]
+fn test_resolve_dispute_split_payment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+    s.escrow.submit_work(&s.freelancer);
+    s.escrow.dispute_escrow(&s.client);
+    s.escrow.resolve_dispute(&s.arbiter, &50);
+
+    assert_eq!(s.escrow.get_status(), STATUS_RESOLVED);
+    assert_eq!(s.token.balance(&s.freelancer), 50_000_000);
+    assert_eq!(s.token.balance(&s.client), 50_000_000);
+    assert_eq!(s.token.balance(&s.escrow.address), 0);
+}
+
+// Test 8: Arbiter sides fully with the freelancer
+#[test]
+fn test_resolve_dispute_full_payment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+    s.escrow.submit_work(&s.freelancer);
+    s.escrow.dispute_escrow(&s.client);
+    s.escrow.resolve_dispute(&s.arbiter, &100);
+
+    assert_eq!(s.token.balance(&s.freelancer), 100_000_000);
+    assert_eq!(s.token.balance(&s.client), 0);
+}
+
+// Test 9: Arbiter refunds the client in full
+#[test]
+fn test_resolve_dispute_full_refund() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+    s.escrow.dispute_escrow(&s.freelancer);
+    s.escrow.resolve_dispute(&s.arbiter, &0);
+
+    assert_eq!(s.token.balance(&s.freelancer), 0);
+    assert_eq!(s.token.balance(&s.client), 100_000_000);
+}
+
+// Test 10: View functions return the escrow details
+#[test]
+fn test_view_functions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_escrow(&env, 100_000_000);
+    s.escrow.create_escrow(&s.client, &s.freelancer, &s.arbiter, &100_000_000, &s.token.address);
+
+    assert_eq!(s.escrow.get_amount(), 100_000_000);
+    assert_eq!(s.escrow.get_client(), Some(s.client.clone()));
+    assert_eq!(s.escrow.get_freelancer(), Some(s.freelancer.clone()));
+    assert_eq!(s.escrow.get_arbiter(), Some(s.arbiter.clone()));
+    assert_eq!(s.escrow.get_status(), STATUS_CREATED);
+}
+
+// Test 11: Percentage above 100 is rejected
+#[test]
+#[should_panic(expected = "Percentage must be between 0 and 100")]
+fn test_invalid_percentage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+    s.escrow.dispute_escrow(&s.client);
+
+    // Should panic
+    s.escrow.resolve_dispute(&s.arbiter, &101);
+}
+
+// Test 12: An escrow can only be created once
+#[test]
+#[should_panic(expected = "Escrow already created")]
+fn test_cannot_create_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_escrow(&env, 200_000_000);
+    s.escrow.create_escrow(&s.client, &s.freelancer, &s.arbiter, &100_000_000, &s.token.address);
+
+    // Should panic
+    s.escrow.create_escrow(&s.client, &s.freelancer, &s.arbiter, &100_000_000, &s.token.address);
+}
+
+// Test 13: Escrow amount must be positive
+#[test]
+#[should_panic(expected = "Escrow amount must be positive")]
+fn test_create_with_zero_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_escrow(&env, 0);
+
+    // Should panic
+    s.escrow.create_escrow(&s.client, &s.freelancer, &s.arbiter, &0, &s.token.address);
+}
+
+// Test 14: Work must be started before it is submitted
+#[test]
+#[should_panic(expected = "Work has not been started")]
+fn test_cannot_submit_without_starting() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_escrow(&env, 100_000_000);
+    s.escrow.create_escrow(&s.client, &s.freelancer, &s.arbiter, &100_000_000, &s.token.address);
+
+    // Should panic
+    s.escrow.submit_work(&s.freelancer);
+}
+
+// Test 15: Work must be submitted before it is approved
+#[test]
+#[should_panic(expected = "Work has not been submitted")]
+fn test_cannot_approve_before_submission() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+
+    // Should panic
+    s.escrow.approve_work(&s.client);
+}
+
+// Test 16: Only a disputed escrow can be resolved
+#[test]
+#[should_panic(expected = "Escrow is not disputed")]
+fn test_cannot_resolve_without_dispute() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+
+    // Should panic
+    s.escrow.resolve_dispute(&s.arbiter, &50);
+}
+
+// Test 17: Only the client can approve
+#[test]
+#[should_panic(expected = "Only the client can approve work")]
+fn test_only_client_can_approve() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+    s.escrow.submit_work(&s.freelancer);
+
+    // Should panic
+    s.escrow.approve_work(&s.freelancer);
+}
+
+// Test 18: Only the arbiter can resolve
+#[test]
+#[should_panic(expected = "Only the arbiter can resolve disputes")]
+fn test_only_arbiter_can_resolve() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_started_escrow(&env);
+    s.escrow.dispute_escrow(&s.freelancer);
+
+    // Should panic
+    s.escrow.resolve_dispute(&s.client, &100);
+}
+
+// Test 19: Someone outside the escrow cannot dispute
+#[test]
+#[should_panic(expected = "Only the client or freelancer can dispute")]
+fn test_third_party_cannot_dispute() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EscrowContract, ());
+    let escrow = EscrowContractClient::new(&env, &contract_id);
+
+    let client = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let third_party = Address::generate(&env);
+
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    token.mint(&client, &100_000_000);
+
+    escrow.create_escrow(&client, &freelancer, &arbiter, &100_000_000, &token.address);
+    escrow.start_work(&freelancer);
+
+    // Should panic
+    escrow.dispute_escrow(&third_party);
+}
+
+// Test 20: Only the named freelancer can start work
+#[test]
+#[should_panic(expected = "Only the freelancer can start work")]
+fn test_wrong_freelancer_cannot_start() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EscrowContract, ());
+    let escrow = EscrowContractClient::new(&env, &contract_id);
+
+    let client = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let wrong_freelancer = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    token.mint(&client, &100_000_000);
+
+    escrow.create_escrow(&client, &freelancer, &arbiter, &100_000_000, &token.address);
+
+    // Should panic
+    escrow.start_work(&wrong_freelancer);
+}
@@ -0,0 +1,96 @@
+#![no_std]
+use soroban_sdk::{contract, contractclient, contractimpl, symbol_short, Address, Bytes, Env, Symbol};
+
+// Storage keys
+const OWNER: Symbol = symbol_short!("OWNER");
+const ACCEPT: Symbol = symbol_short!("ACCEPT");
+const CALLS: Symbol = symbol_short!("CALLS");
+const LAST_INITIATOR: Symbol = symbol_short!("LAST_INIT");
+const LAST_AMOUNT: Symbol = symbol_short!("LAST_AMT");
+const LAST_FEE: Symbol = symbol_short!("LAST_FEE");
+
+// Bagian TokenContract yang dipakai receiver untuk menyetujui pelunasan
+#[contractclient(name = "TokenClient")]
+pub trait Token {
+    fn approve(env: Env, from: Address, spender: Address, amount: i128);
+}
+
+// Receiver contoh untuk flash_mint di TokenContract, dipakai di test.
+// Soroban tidak mengizinkan re-entry, jadi receiver tidak bisa memanggil
+// token selama exec_op. Pelunasan disetujui lebih dulu lewat
+// approve_repayment, lalu token menarik amount + fee dari allowance itu.
+// Karena siapa pun bisa memanggil flash_mint, exec_op hanya menerima flash
+// mint yang dimulai owner; kalau tidak, orang lain bisa menghabiskan
+// allowance itu untuk fee.
+#[contract]
+pub struct FlashReceiver;
+
+#[contractimpl]
+impl FlashReceiver {
+
+    // Initialize receiver dengan owner yang boleh memulai flash mint
+    pub fn initialize(env: Env, owner: Address) {
+        owner.require_auth();
+
+        if env.storage().instance().has(&OWNER) {
+            panic!("Receiver sudah diinisialisasi");
+        }
+
+        env.storage().instance().set(&OWNER, &owner);
+    }
+
+    // Owner mengatur apakah exec_op menyetujui flash mint berikutnya
+    pub fn set_accept(env: Env, accept: bool) {
+        Self::read_owner(&env).require_auth();
+
+        env.storage().instance().set(&ACCEPT, &accept);
+    }
+
+    // Izinkan `token` menarik sampai `amount` dari receiver untuk pelunasan
+    // flash mint berikutnya. Dipanggil sebelum flash_mint, di call terpisah.
+    pub fn approve_repayment(env: Env, token: Address, amount: i128) {
+        Self::read_owner(&env).require_auth();
+
+        TokenClient::new(&env, &token).approve(&env.current_contract_address(), &token, &amount);
+    }
+
+    // Callback dari flash_mint; return true berarti amount + fee boleh ditarik.
+    // Flash mint dari selain owner selalu ditolak.
+    pub fn exec_op(env: Env, initiator: Address, amount: i128, fee: i128, _data: Bytes) -> bool {
+        if initiator != Self::read_owner(&env) {
+            return false;
+        }
+
+        let calls: u32 = env.storage().instance().get(&CALLS).unwrap_or(0);
+        env.storage().instance().set(&CALLS, &(calls + 1));
+        env.storage().instance().set(&LAST_INITIATOR, &initiator);
+        env.storage().instance().set(&LAST_AMOUNT, &amount);
+        env.storage().instance().set(&LAST_FEE, &fee);
+
+        env.storage().instance().get(&ACCEPT).unwrap_or(true)
+    }
+
+    // Get jumlah exec_op yang sudah diterima
+    pub fn get_calls(env: Env) -> u32 {
+        env.storage().instance().get(&CALLS).unwrap_or(0)
+    }
+
+    // Get initiator dari exec_op terakhir
+    pub fn get_last_initiator(env: Env) -> Option<Address> {
+        env.storage().instance().get(&LAST_INITIATOR)
+    }
+
+    // Get amount dari exec_op terakhir
+    pub fn get_last_amount(env: Env) -> i128 {
+        env.storage().instance().get(&LAST_AMOUNT).unwrap_or(0)
+    }
+
+    // Get fee dari exec_op terakhir
+    pub fn get_last_fee(env: Env) -> i128 {
+        env.storage().instance().get(&LAST_FEE).unwrap_or(0)
+    }
+
+    fn read_owner(env: &Env) -> Address {
+        env.storage().instance().get(&OWNER).unwrap_or_else(|| panic!("Receiver belum diinisialisasi"))
+    }
+}
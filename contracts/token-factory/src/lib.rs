@@ -0,0 +1,139 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env,
+    IntoVal, String, Symbol, Val, Vec,
+};
+
+// Storage keys
+const ADMIN: Symbol = symbol_short!("admin");
+const WASM_HASH: Symbol = symbol_short!("wasm_hash");
+const TOKEN_COUNT: Symbol = symbol_short!("tok_count");
+
+// Registry diperpanjang sampai ~30 hari setiap ditulis, saat sisa TTL < ~29 hari
+const REGISTRY_TTL_THRESHOLD: u32 = 500_000;
+const REGISTRY_TTL_EXTEND: u32 = 518_400;
+
+// Key persistent storage: satu entry per token dan per creator, supaya biaya
+// deploy_token tidak ikut membesar bersama jumlah token yang pernah di-deploy
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Token(u32),
+    CreatorTokens(Address),
+}
+
+// Bagian interface TokenContract yang dibutuhkan factory
+#[contractclient(name = "TokenClient")]
+pub trait TokenInterface {
+    fn initialize(env: Env, admin: Address, name: String, symbol: String, total_supply: i128);
+}
+
+#[contract]
+pub struct TokenFactory;
+
+#[contractimpl]
+impl TokenFactory {
+
+    // Initialize factory dengan WASM hash TokenContract yang sudah di-upload
+    pub fn initialize(env: Env, admin: Address, wasm_hash: BytesN<32>) {
+        admin.require_auth();
+
+        if env.storage().instance().has(&ADMIN) {
+            panic!("Factory sudah diinisialisasi");
+        }
+
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&WASM_HASH, &wasm_hash);
+    }
+
+    // Admin mengganti WASM hash untuk deployment berikutnya
+    pub fn set_wasm_hash(env: Env, wasm_hash: BytesN<32>) {
+        let admin: Address = env.storage().instance().get(&ADMIN).unwrap();
+        admin.require_auth();
+
+        env.storage().instance().set(&WASM_HASH, &wasm_hash);
+    }
+
+    // Deploy TokenContract baru di address yang ditentukan creator dan salt,
+    // lalu langsung initialize dengan creator sebagai admin, dalam satu transaksi.
+    // Salt milik creator lain menghasilkan address lain, jadi deployment yang
+    // terlihat di mempool tidak bisa didahului.
+    pub fn deploy_token(
+        env: Env,
+        creator: Address,
+        salt: BytesN<32>,
+        name: String,
+        symbol: String,
+        total_supply: i128,
+    ) -> Address {
+        creator.require_auth();
+
+        let wasm_hash: BytesN<32> = env.storage().instance()
+            .get(&WASM_HASH)
+            .unwrap_or_else(|| panic!("Factory belum diinisialisasi"));
+
+        let token_address = env.deployer()
+            .with_current_contract(Self::creator_salt(&env, &creator, salt))
+            .deploy_v2(wasm_hash, ());
+
+        let token = TokenClient::new(&env, &token_address);
+        token.initialize(&creator, &name, &symbol, &total_supply);
+
+        let count = Self::get_token_count(env.clone());
+        Self::write_entry(&env, &DataKey::Token(count), &token_address);
+        env.storage().instance().set(&TOKEN_COUNT, &(count + 1));
+
+        let mut created = Self::get_tokens_by_creator(env.clone(), creator.clone());
+        created.push_back(token_address.clone());
+        Self::write_entry(&env, &DataKey::CreatorTokens(creator), &created);
+
+        token_address
+    }
+
+    // Get address yang akan dipakai deploy_token untuk creator dan salt ini
+    pub fn predict_address(env: Env, creator: Address, salt: BytesN<32>) -> Address {
+        env.deployer()
+            .with_current_contract(Self::creator_salt(&env, &creator, salt))
+            .deployed_address()
+    }
+
+    // Get jumlah token yang pernah di-deploy factory ini
+    pub fn get_token_count(env: Env) -> u32 {
+        env.storage().instance().get(&TOKEN_COUNT).unwrap_or(0)
+    }
+
+    // Get token yang di-deploy factory ini mulai dari index `start`, maksimal `limit` address
+    pub fn get_tokens(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        let end = start.saturating_add(limit).min(Self::get_token_count(env.clone()));
+        let mut result = Vec::new(&env);
+        for index in start..end {
+            result.push_back(env.storage().persistent().get(&DataKey::Token(index)).unwrap());
+        }
+        result
+    }
+
+    // Get token yang di-deploy oleh creator tertentu
+    pub fn get_tokens_by_creator(env: Env, creator: Address) -> Vec<Address> {
+        env.storage().persistent().get(&DataKey::CreatorTokens(creator)).unwrap_or(Vec::new(&env))
+    }
+
+    // Get WASM hash yang dipakai untuk deployment
+    pub fn get_wasm_hash(env: Env) -> BytesN<32> {
+        env.storage().instance().get(&WASM_HASH).unwrap()
+    }
+
+    fn write_entry<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+        env.storage().persistent().set(key, value);
+        env.storage().persistent().extend_ttl(key, REGISTRY_TTL_THRESHOLD, REGISTRY_TTL_EXTEND);
+    }
+
+    // Salt deployment = sha256(XDR creator || salt)
+    fn creator_salt(env: &Env, creator: &Address, salt: BytesN<32>) -> BytesN<32> {
+        let mut preimage = creator.clone().to_xdr(env);
+        preimage.append(&Bytes::from(salt));
+        env.crypto().sha256(&preimage).into()
+    }
+}
+
+#[cfg(test)]
+mod test;
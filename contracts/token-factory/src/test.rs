@@ -0,0 +1,161 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+
+// Helper function to set up a factory; predicting addresses needs no WASM
+fn setup_factory_with_hash<'a>(env: &Env, wasm_hash: &BytesN<32>) -> TokenFactoryClient<'a> {
+    let contract_id = env.register(TokenFactory, ());
+    let client = TokenFactoryClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    client.initialize(&admin, wasm_hash);
+    client
+}
+
+#[test]
+fn test_predict_address_per_creator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let factory = setup_factory_with_hash(&env, &BytesN::from_array(&env, &[0u8; 32]));
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[1u8; 32]);
+
+    // Same creator and salt always map to the same address
+    let predicted = factory.predict_address(&alice, &salt);
+    assert_eq!(factory.predict_address(&alice, &salt), predicted);
+
+    // Another creator reusing the salt lands somewhere else
+    assert!(factory.predict_address(&bob, &salt) != predicted);
+    assert!(factory.predict_address(&alice, &BytesN::from_array(&env, &[2u8; 32])) != predicted);
+}
+
+// Deployment tests import the TokenContract WASM, so they only build with
+// `--features token-wasm` after `make build` (see Makefile)
+#[cfg(feature = "token-wasm")]
+mod deploy {
+    use super::*;
+    use soroban_sdk::String;
+
+    mod token_contract {
+        soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/token.wasm");
+    }
+
+    // Helper function to set up a factory with the token WASM uploaded
+    fn setup_factory<'a>(env: &Env) -> TokenFactoryClient<'a> {
+        let wasm_hash = env.deployer().upload_contract_wasm(token_contract::WASM);
+        setup_factory_with_hash(env, &wasm_hash)
+    }
+
+    #[test]
+    fn test_deploy_token() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let factory = setup_factory(&env);
+        let creator = Address::generate(&env);
+        let salt = BytesN::from_array(&env, &[1u8; 32]);
+
+        let predicted = factory.predict_address(&creator, &salt);
+        let name = String::from_str(&env, "Team Token");
+        let symbol = String::from_str(&env, "TEAM");
+        let token_address = factory.deploy_token(&creator, &salt, &name, &symbol, &1_000_000);
+
+        // Deployed where predicted and already initialized
+        assert_eq!(token_address, predicted);
+        let token = token_contract::Client::new(&env, &token_address);
+        assert_eq!(token.get_name(), name);
+        assert_eq!(token.get_symbol(), symbol);
+        assert_eq!(token.balance(&creator), 1_000_000);
+
+        assert_eq!(factory.get_token_count(), 1);
+        assert_eq!(factory.get_tokens_by_creator(&creator).get(0), Some(token_address));
+    }
+
+    #[test]
+    fn test_registry_by_creator() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let factory = setup_factory(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let name = String::from_str(&env, "Team Token");
+        let symbol = String::from_str(&env, "TEAM");
+
+        factory.deploy_token(&alice, &BytesN::from_array(&env, &[1u8; 32]), &name, &symbol, &1_000);
+        factory.deploy_token(&alice, &BytesN::from_array(&env, &[2u8; 32]), &name, &symbol, &1_000);
+        factory.deploy_token(&bob, &BytesN::from_array(&env, &[3u8; 32]), &name, &symbol, &1_000);
+
+        assert_eq!(factory.get_token_count(), 3);
+        assert_eq!(factory.get_tokens(&1, &10).len(), 2);
+        assert_eq!(factory.get_tokens(&0, &1).get(0), factory.get_tokens_by_creator(&alice).get(0));
+        assert_eq!(factory.get_tokens_by_creator(&alice).len(), 2);
+        assert_eq!(factory.get_tokens_by_creator(&bob).len(), 1);
+        assert_eq!(factory.get_tokens_by_creator(&Address::generate(&env)).len(), 0);
+    }
+
+    #[test]
+    fn test_deploy_same_salt_twice() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let factory = setup_factory(&env);
+        let creator = Address::generate(&env);
+        let salt = BytesN::from_array(&env, &[1u8; 32]);
+        let name = String::from_str(&env, "Team Token");
+        let symbol = String::from_str(&env, "TEAM");
+
+        factory.deploy_token(&creator, &salt, &name, &symbol, &1_000);
+
+        // Address is already taken
+        let result = factory.try_deploy_token(&creator, &salt, &name, &symbol, &1_000);
+        assert!(result.is_err());
+        assert_eq!(factory.get_token_count(), 1);
+    }
+
+    #[test]
+    fn test_deploy_rolls_back_when_initialize_fails() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let factory = setup_factory(&env);
+        let creator = Address::generate(&env);
+        let salt = BytesN::from_array(&env, &[1u8; 32]);
+        let name = String::from_str(&env, "Bad Token");
+        let symbol = String::from_str(&env, "BAD");
+
+        // Zero supply fails initialize, so the deployment is reverted too
+        let result = factory.try_deploy_token(&creator, &salt, &name, &symbol, &0);
+        assert!(result.is_err());
+        assert_eq!(factory.get_token_count(), 0);
+
+        // The salt is still free to use
+        let token_address = factory.deploy_token(&creator, &salt, &name, &symbol, &1_000);
+        assert_eq!(token_address, factory.predict_address(&creator, &salt));
+    }
+
+    #[test]
+    fn test_deploy_not_front_run_by_salt() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let factory = setup_factory(&env);
+        let creator = Address::generate(&env);
+        let attacker = Address::generate(&env);
+        let salt = BytesN::from_array(&env, &[1u8; 32]);
+        let name = String::from_str(&env, "Team Token");
+        let symbol = String::from_str(&env, "TEAM");
+
+        // Copying the creator's salt does not take the creator's address
+        let predicted = factory.predict_address(&creator, &salt);
+        let copied = factory.deploy_token(&attacker, &salt, &name, &symbol, &1_000);
+        assert!(copied != predicted);
+
+        let token_address = factory.deploy_token(&creator, &salt, &name, &symbol, &1_000);
+        assert_eq!(token_address, predicted);
+        assert_eq!(token_contract::Client::new(&env, &token_address).balance(&creator), 1_000);
+    }
+}
@@ -0,0 +1,73 @@
+use soroban_sdk::{contractevent, contractimpl, symbol_short, Address, Env, Symbol};
+
+use crate::{DataKey, TokenContract, TokenContractArgs, TokenContractClient, ADMIN};
+
+// Storage keys
+const AUTH_REQUIRED: Symbol = symbol_short!("AUTH_REQ");
+
+// Event saat mode AUTH_REQUIRED dinyalakan/dimatikan
+#[contractevent(data_format = "single-value")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthRequiredChanged {
+    pub required: bool,
+}
+
+// Event saat status authorized sebuah address berubah
+#[contractevent(data_format = "single-value")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthorizedChanged {
+    #[topic]
+    pub id: Address,
+    pub authorized: bool,
+}
+
+#[contractimpl]
+impl TokenContract {
+
+    // Admin menyalakan/mematikan mode di mana hanya address authorized
+    // yang boleh menerima dan mengirim token (mirip flag AUTH_REQUIRED di Stellar)
+    pub fn set_auth_required(env: Env, required: bool) {
+        let admin: Address = env.storage().instance().get(&ADMIN).unwrap();
+        admin.require_auth();
+
+        env.storage().instance().set(&AUTH_REQUIRED, &required);
+        AuthRequiredChanged { required }.publish(&env);
+    }
+
+    // Get apakah mode AUTH_REQUIRED aktif
+    pub fn get_auth_required(env: Env) -> bool {
+        env.storage().instance().get(&AUTH_REQUIRED).unwrap_or(false)
+    }
+
+    // Admin menandai address sebagai authorized atau tidak
+    pub fn set_authorized(env: Env, id: Address, authorize: bool) {
+        let admin: Address = env.storage().instance().get(&ADMIN).unwrap();
+        admin.require_auth();
+
+        env.storage().persistent().set(&DataKey::Authorized(id.clone()), &authorize);
+
+        AuthorizedChanged { id, authorized: authorize }.publish(&env);
+    }
+
+    // Get apakah address boleh menerima dan mengirim token saat ini
+    pub fn authorized(env: Env, id: Address) -> bool {
+        if !Self::get_auth_required(env.clone()) {
+            return true;
+        }
+
+        // Admin dan escrow contract sendiri selalu boleh
+        let admin: Address = env.storage().instance().get(&ADMIN).unwrap();
+        if id == admin || id == env.current_contract_address() {
+            return true;
+        }
+
+        env.storage().persistent().get(&DataKey::Authorized(id)).unwrap_or(false)
+    }
+
+    // Dipanggil di setiap jalur yang menambah atau mengurangi balance
+    pub(crate) fn require_authorized(env: &Env, id: &Address) {
+        if !Self::authorized(env.clone(), id.clone()) {
+            panic!("Address belum authorized");
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use soroban_sdk::{contractimpl, symbol_short, token, Address, Env, Symbol};
+
+use crate::{DataKey, TokenContract, TokenContractArgs, TokenContractClient, ADMIN, TOTAL};
+
+// Storage keys
+const DIV_TOKEN: Symbol = symbol_short!("DIV_TOKEN");
+const DIV_PER_SHARE: Symbol = symbol_short!("DIV_SHARE");
+const DIV_REMAINDER: Symbol = symbol_short!("DIV_REM");
+
+// Skala accumulator supaya pembagian per token tidak langsung jadi 0
+const MAGNITUDE: i128 = 1_000_000_000_000;
+
+#[contractimpl]
+impl TokenContract {
+
+    // Admin menentukan token yang dipakai untuk membayar dividen (sekali saja)
+    pub fn set_dividend_token(env: Env, dividend_token: Address) {
+        let admin: Address = env.storage().instance().get(&ADMIN).unwrap();
+        admin.require_auth();
+
+        if env.storage().instance().has(&DIV_TOKEN) {
+            panic!("Dividend token sudah diset");
+        }
+
+        env.storage().instance().set(&DIV_TOKEN, &dividend_token);
+    }
+
+    // Get token dividen (None kalau belum diset)
+    pub fn get_dividend_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DIV_TOKEN)
+    }
+
+    // Setor dividen; dibagi ke semua holder sesuai balance saat ini
+    pub fn deposit_dividends(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount harus lebih dari 0");
+        }
+
+        // Token yang di-escrow contract sendiri (HTLC, stream) tidak ikut dapat dividen
+        let contract_address = env.current_contract_address();
+        let total: i128 = env.storage().instance().get(&TOTAL).unwrap();
+        let eligible = total - Self::read_balance(&env, &contract_address);
+        if eligible <= 0 {
+            panic!("Belum ada holder");
+        }
+
+        let dividend_token = token::Client::new(&env, &Self::require_dividend_token(&env));
+        dividend_token.transfer(&from, &contract_address, &amount);
+
+        // Sisa pembagian dibawa ke setoran berikutnya, jadi setoran kecil
+        // terhadap supply besar tetap sampai ke holder
+        let remainder: i128 = env.storage().instance().get(&DIV_REMAINDER).unwrap_or(0);
+        let scaled = amount * MAGNITUDE + remainder;
+
+        let per_share: i128 = env.storage().instance().get(&DIV_PER_SHARE).unwrap_or(0);
+        env.storage().instance().set(&DIV_PER_SHARE, &(per_share + scaled / eligible));
+        env.storage().instance().set(&DIV_REMAINDER, &(scaled % eligible));
+    }
+
+    // Get dividen yang belum di-claim oleh holder
+    pub fn pending_dividends(env: Env, holder: Address) -> i128 {
+        Self::accumulated_dividends(&env, &holder) - Self::read_claimed(&env, &holder)
+    }
+
+    // Kirim semua dividen yang belum di-claim ke holder, return jumlahnya
+    pub fn claim_dividends(env: Env, holder: Address) -> i128 {
+        holder.require_auth();
+
+        let amount = Self::pending_dividends(env.clone(), holder.clone());
+        if amount <= 0 {
+            return 0;
+        }
+
+        let claimed = Self::read_claimed(&env, &holder) + amount;
+        env.storage().persistent().set(&DataKey::DivClaimed(holder.clone()), &claimed);
+
+        let dividend_token = token::Client::new(&env, &Self::require_dividend_token(&env));
+        dividend_token.transfer(&env.current_contract_address(), &holder, &amount);
+
+        amount
+    }
+
+    // Dipanggil setiap balance berubah supaya dividen yang sudah terkumpul
+    // tidak ikut pindah bersama token
+    pub(crate) fn adjust_dividend_correction(env: &Env, id: &Address, delta: i128) {
+        let per_share: i128 = env.storage().instance().get(&DIV_PER_SHARE).unwrap_or(0);
+        if per_share == 0 || *id == env.current_contract_address() {
+            return;
+        }
+
+        let key = DataKey::DivCorrection(id.clone());
+        let correction: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(correction - per_share * delta));
+    }
+
+    fn accumulated_dividends(env: &Env, holder: &Address) -> i128 {
+        if *holder == env.current_contract_address() {
+            return 0;
+        }
+
+        let per_share: i128 = env.storage().instance().get(&DIV_PER_SHARE).unwrap_or(0);
+        let correction: i128 = env.storage().persistent()
+            .get(&DataKey::DivCorrection(holder.clone()))
+            .unwrap_or(0);
+
+        (per_share * Self::read_balance(env, holder) + correction) / MAGNITUDE
+    }
+
+    fn read_claimed(env: &Env, holder: &Address) -> i128 {
+        env.storage().persistent().get(&DataKey::DivClaimed(holder.clone())).unwrap_or(0)
+    }
+
+    fn require_dividend_token(env: &Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DIV_TOKEN)
+            .unwrap_or_else(|| panic!("Dividend token belum diset"))
+    }
+}
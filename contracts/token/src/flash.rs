@@ -0,0 +1,82 @@
+use soroban_sdk::{contractclient, contractimpl, symbol_short, Address, Bytes, Env, Symbol};
+
+use crate::{TokenContract, TokenContractArgs, TokenContractClient, ADMIN, TOTAL};
+
+// Storage keys
+const FLASH_FEE: Symbol = symbol_short!("FLASH_FEE");
+
+// Fee maksimum 10% (dalam basis points)
+pub const MAX_FLASH_FEE_BPS: u32 = 1_000;
+
+// Interface yang harus diimplementasikan contract penerima flash_mint.
+// Soroban tidak mengizinkan re-entry: selama exec_op receiver (dan contract
+// lain yang dipanggilnya) tidak bisa memanggil token ini, termasuk untuk
+// memakai token yang baru dicetak.
+//
+// Siapa pun bisa memanggil flash_mint untuk receiver mana pun, dan pelunasan
+// diambil dari allowance yang sudah diberikan receiver. exec_op wajib
+// memeriksa `initiator` dan menolak (return false) flash mint yang tidak
+// dimulai pihak yang dipercaya, supaya allowance itu tidak dipakai orang
+// lain untuk membayar fee.
+#[contractclient(name = "FlashMintReceiverClient")]
+pub trait FlashMintReceiver {
+    fn exec_op(env: Env, initiator: Address, amount: i128, fee: i128, data: Bytes) -> bool;
+}
+
+#[contractimpl]
+impl TokenContract {
+
+    // Admin mengatur fee flash mint dalam basis points
+    pub fn set_flash_fee(env: Env, fee_bps: u32) {
+        let admin: Address = env.storage().instance().get(&ADMIN).unwrap();
+        admin.require_auth();
+
+        if fee_bps > MAX_FLASH_FEE_BPS {
+            panic!("Fee flash mint terlalu besar");
+        }
+
+        env.storage().instance().set(&FLASH_FEE, &fee_bps);
+    }
+
+    // Get fee flash mint dalam basis points
+    pub fn get_flash_fee(env: Env) -> u32 {
+        env.storage().instance().get(&FLASH_FEE).unwrap_or(0)
+    }
+
+    // Get fee yang harus dibayar untuk flash mint sebesar `amount`
+    pub fn flash_fee(env: Env, amount: i128) -> i128 {
+        amount * Self::get_flash_fee(env) as i128 / 10_000
+    }
+
+    // Cetak `amount` ke receiver atas permintaan `initiator`, panggil exec_op,
+    // lalu tarik amount + fee dari allowance yang diberikan receiver ke token
+    // ini sebelumnya (approve tidak bisa dipanggil di dalam exec_op karena
+    // re-entry). Amount dibakar, fee ke admin.
+    pub fn flash_mint(env: Env, initiator: Address, receiver: Address, amount: i128, data: Bytes) {
+        initiator.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount harus lebih dari 0");
+        }
+
+        let fee = Self::flash_fee(env.clone(), amount);
+        let total: i128 = env.storage().instance().get(&TOTAL).unwrap();
+
+        Self::receive_balance(&env, &receiver, amount);
+        env.storage().instance().set(&TOTAL, &(total + amount));
+
+        let receiver_client = FlashMintReceiverClient::new(&env, &receiver);
+        if !receiver_client.exec_op(&initiator, &amount, &fee, &data) {
+            panic!("Flash mint ditolak receiver");
+        }
+
+        Self::spend_allowance(&env, &receiver, &env.current_contract_address(), amount + fee);
+        Self::spend_balance(&env, &receiver, amount + fee);
+        env.storage().instance().set(&TOTAL, &total);
+
+        if fee > 0 {
+            let admin: Address = env.storage().instance().get(&ADMIN).unwrap();
+            Self::receive_balance(&env, &admin, fee);
+        }
+    }
+}
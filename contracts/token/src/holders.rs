@@ -0,0 +1,143 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::{DataKey, TokenContract, TokenContractArgs, TokenContractClient};
+
+// Storage keys
+const HOLDER_COUNT: Symbol = symbol_short!("HOLDR_CNT");
+const TOP_HOLDERS: Symbol = symbol_short!("TOP_HOLDR");
+const TOP_FLOOR: Symbol = symbol_short!("TOP_FLOOR");
+
+// Jumlah maksimum holder yang dikembalikan top_holders
+pub const TOP_HOLDERS_LIMIT: u32 = 10;
+
+// Kandidat yang disimpan, supaya slot yang kosong bisa diisi ulang tanpa
+// scan semua holder
+const TOP_CANDIDATES: u32 = 2 * TOP_HOLDERS_LIMIT;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HolderBalance {
+    pub holder: Address,
+    pub balance: i128,
+}
+
+// Hasil top_holders. `complete` false berarti masih ada holder lain, tapi
+// peringkatnya tidak diketahui tanpa scan semua holder, jadi `holders` lebih
+// pendek dari yang diminta.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TopHolders {
+    pub holders: Vec<HolderBalance>,
+    pub complete: bool,
+}
+
+#[contractimpl]
+impl TokenContract {
+
+    // Get jumlah address dengan balance > 0
+    pub fn holder_count(env: Env) -> u32 {
+        env.storage().instance().get(&HOLDER_COUNT).unwrap_or(0)
+    }
+
+    // Get daftar holder mulai dari index `start`, maksimal `limit` address
+    pub fn holders(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        let end = start.saturating_add(limit).min(Self::holder_count(env.clone()));
+        let mut result = Vec::new(&env);
+        for position in start..end {
+            result.push_back(Self::read_holder(&env, position));
+        }
+        result
+    }
+
+    // Get `n` holder terbesar, urut dari balance tertinggi (maks TOP_HOLDERS_LIMIT).
+    // Hanya kandidat yang pasti di atas semua holder di luar daftar yang
+    // dikembalikan; kalau itu kurang dari `n` padahal holder lain masih ada
+    // (setelah banyak anggota turun), hasilnya ditandai tidak lengkap.
+    pub fn top_holders(env: Env, n: u32) -> TopHolders {
+        let floor: i128 = env.storage().instance().get(&TOP_FLOOR).unwrap_or(0);
+        let limit = n.min(TOP_HOLDERS_LIMIT);
+
+        let mut result = Vec::new(&env);
+        for entry in Self::read_top_holders(&env).iter() {
+            if result.len() == limit || entry.balance < floor {
+                break;
+            }
+            result.push_back(entry);
+        }
+
+        let complete = result.len() == limit || Self::holder_count(env.clone()) <= result.len();
+        TopHolders { holders: result, complete }
+    }
+
+    // Dipanggil setiap balance berubah, setelah balance baru disimpan.
+    // Token yang di-escrow contract sendiri (HTLC, stream) tidak dihitung sebagai holder.
+    pub(crate) fn update_holder_index(env: &Env, id: &Address, old_balance: i128, new_balance: i128) {
+        if *id == env.current_contract_address() {
+            return;
+        }
+
+        let storage = env.storage().persistent();
+        let count = Self::holder_count(env.clone());
+
+        if old_balance <= 0 && new_balance > 0 {
+            storage.set(&DataKey::Holder(count), id);
+            storage.set(&DataKey::HolderIndex(id.clone()), &count);
+            env.storage().instance().set(&HOLDER_COUNT, &(count + 1));
+        } else if old_balance > 0 && new_balance <= 0 {
+            // Swap-remove: holder terakhir pindah ke slot yang kosong
+            let position: u32 = storage.get(&DataKey::HolderIndex(id.clone())).unwrap();
+            let last = Self::read_holder(env, count - 1);
+            if last != *id {
+                storage.set(&DataKey::Holder(position), &last);
+                storage.set(&DataKey::HolderIndex(last), &position);
+            }
+            storage.remove(&DataKey::Holder(count - 1));
+            storage.remove(&DataKey::HolderIndex(id.clone()));
+            env.storage().instance().set(&HOLDER_COUNT, &(count - 1));
+        }
+
+        if old_balance != new_balance {
+            Self::update_top_holders(env, id, new_balance);
+        }
+    }
+
+    // Kandidat diurutkan dari balance tertinggi dan dibatasi TOP_CANDIDATES.
+    // Floor adalah balance tertinggi yang pernah tidak muat di daftar: holder
+    // di luar daftar tidak pernah punya balance di atas floor.
+    fn update_top_holders(env: &Env, id: &Address, new_balance: i128) {
+        let mut top = Self::read_top_holders(env);
+        let mut floor: i128 = env.storage().instance().get(&TOP_FLOOR).unwrap_or(0);
+
+        if let Some(position) = top.iter().position(|entry| entry.holder == *id) {
+            top.remove(position as u32);
+        }
+
+        if new_balance > 0 {
+            let position = top.iter()
+                .position(|entry| new_balance > entry.balance)
+                .map_or(top.len(), |position| position as u32);
+            top.insert(position, HolderBalance { holder: id.clone(), balance: new_balance });
+
+            if top.len() > TOP_CANDIDATES {
+                let evicted = top.pop_back().unwrap();
+                floor = floor.max(evicted.balance);
+            }
+        }
+
+        // Semua holder ada di daftar, tidak ada balance tersembunyi di luar
+        if Self::holder_count(env.clone()) <= top.len() {
+            floor = 0;
+        }
+
+        env.storage().instance().set(&TOP_HOLDERS, &top);
+        env.storage().instance().set(&TOP_FLOOR, &floor);
+    }
+
+    fn read_holder(env: &Env, position: u32) -> Address {
+        env.storage().persistent().get(&DataKey::Holder(position)).unwrap()
+    }
+
+    fn read_top_holders(env: &Env) -> Vec<HolderBalance> {
+        env.storage().instance().get(&TOP_HOLDERS).unwrap_or(Vec::new(env))
+    }
+}
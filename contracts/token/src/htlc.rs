@@ -0,0 +1,101 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+use crate::{DataKey, TokenContract, TokenContractArgs, TokenContractClient};
+
+// Storage keys
+const LOCK_ID: Symbol = symbol_short!("LOCK_ID");
+
+// Hash time-locked transfer: token di-escrow oleh contract sampai
+// recipient menunjukkan preimage, atau sender menarik kembali setelah timelock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HashLock {
+    pub sender: Address,
+    pub recipient: Address,
+    pub amount: i128,
+    pub hashlock: BytesN<32>,
+    pub timelock: u64,
+}
+
+#[contractimpl]
+impl TokenContract {
+
+    // Escrow token sampai sha256(preimage) == hashlock atau timelock lewat
+    pub fn lock(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        amount: i128,
+        hashlock: BytesN<32>,
+        timelock: u64,
+    ) -> u32 {
+        sender.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount harus lebih dari 0");
+        }
+        if timelock <= env.ledger().timestamp() {
+            panic!("Timelock harus di masa depan");
+        }
+
+        Self::check_spend_limit(&env, &sender, amount);
+        Self::spend_balance(&env, &sender, amount);
+        Self::receive_balance(&env, &env.current_contract_address(), amount);
+
+        let id: u32 = env.storage().instance().get(&LOCK_ID).unwrap_or(0);
+        Self::index_add(&env, &DataKey::LocksOf(sender.clone()), id);
+        Self::index_add(&env, &DataKey::LocksOf(recipient.clone()), id);
+        env.storage().persistent().set(&DataKey::Lock(id), &HashLock { sender, recipient, amount, hashlock, timelock });
+        env.storage().instance().set(&LOCK_ID, &(id + 1));
+
+        id
+    }
+
+    // Lepaskan token ke recipient kalau preimage cocok dan timelock belum lewat
+    pub fn claim(env: Env, id: u32, preimage: Bytes) {
+        let lock = Self::get_lock(env.clone(), id).unwrap_or_else(|| panic!("Lock tidak ditemukan"));
+
+        if env.ledger().timestamp() > lock.timelock {
+            panic!("Timelock sudah lewat");
+        }
+
+        let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+        if hash != lock.hashlock {
+            panic!("Preimage tidak cocok");
+        }
+
+        Self::remove_lock(&env, id, &lock);
+
+        Self::spend_balance(&env, &env.current_contract_address(), lock.amount);
+        Self::receive_balance(&env, &lock.recipient, lock.amount);
+    }
+
+    // Kembalikan token ke sender setelah timelock lewat
+    pub fn reclaim(env: Env, id: u32) {
+        let lock = Self::get_lock(env.clone(), id).unwrap_or_else(|| panic!("Lock tidak ditemukan"));
+
+        if env.ledger().timestamp() <= lock.timelock {
+            panic!("Timelock belum lewat");
+        }
+
+        Self::remove_lock(&env, id, &lock);
+
+        Self::return_escrow(&env, &lock.sender, lock.amount);
+    }
+
+    // Get detail lock yang masih aktif
+    pub fn get_lock(env: Env, id: u32) -> Option<HashLock> {
+        env.storage().persistent().get(&DataKey::Lock(id))
+    }
+
+    // Get id semua lock aktif di mana `id` adalah sender atau recipient
+    pub fn locks_of(env: Env, id: Address) -> Vec<u32> {
+        env.storage().persistent().get(&DataKey::LocksOf(id)).unwrap_or(Vec::new(&env))
+    }
+
+    fn remove_lock(env: &Env, id: u32, lock: &HashLock) {
+        env.storage().persistent().remove(&DataKey::Lock(id));
+        Self::index_remove(env, &DataKey::LocksOf(lock.sender.clone()), id);
+        Self::index_remove(env, &DataKey::LocksOf(lock.recipient.clone()), id);
+    }
+}
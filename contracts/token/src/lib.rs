@@ -1,11 +1,57 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, symbol_short};
+use soroban_sdk::{contract, contractclient, contractimpl, contracttype, token, Address, Bytes, Env, String, Vec, symbol_short};
+
+mod authorization;
+mod dividend;
+mod flash;
+mod holders;
+mod htlc;
+mod limits;
+mod stream;
+
+pub use authorization::{AuthRequiredChanged, AuthorizedChanged};
+pub use flash::{FlashMintReceiver, FlashMintReceiverClient, MAX_FLASH_FEE_BPS};
+pub use holders::{HolderBalance, TopHolders, TOP_HOLDERS_LIMIT};
+pub use htlc::HashLock;
+pub use limits::{SpendLimit, LIMIT_WINDOW};
+pub use stream::Stream;
 
 // Storage keys
-const BALANCE: soroban_sdk::Symbol = symbol_short!("BALANCE");
+pub(crate) const ADMIN: soroban_sdk::Symbol = symbol_short!("ADMIN");
 const NAME: soroban_sdk::Symbol = symbol_short!("NAME");
 const SYMBOL: soroban_sdk::Symbol = symbol_short!("SYMBOL");
-const TOTAL: soroban_sdk::Symbol = symbol_short!("TOTAL");
+pub(crate) const TOTAL: soroban_sdk::Symbol = symbol_short!("TOTAL");
+const UNDERLYING: soroban_sdk::Symbol = symbol_short!("UNDERLY");
+
+// Balance diperpanjang sampai ~30 hari setiap disentuh, saat sisa TTL < ~29 hari
+const BALANCE_TTL_THRESHOLD: u32 = 500_000;
+const BALANCE_TTL_EXTEND: u32 = 518_400;
+
+// Key persistent storage: satu entry per address/id, supaya biaya baca/tulis
+// tidak ikut membesar bersama jumlah holder
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Balance(Address),
+    Allowance(Address, Address),
+    Lock(u32),
+    LocksOf(Address),
+    Stream(u32),
+    StreamsOf(Address),
+    DivCorrection(Address),
+    DivClaimed(Address),
+    Authorized(Address),
+    SpendLimit(Address),
+    Outflows(Address),
+    Holder(u32),
+    HolderIndex(Address),
+}
+
+// Interface yang harus diimplementasikan contract penerima transfer_and_call
+#[contractclient(name = "TokenReceiverClient")]
+pub trait TokenReceiver {
+    fn on_token_received(env: Env, from: Address, amount: i128, data: Bytes);
+}
 
 // Contract struct
 #[contract]
@@ -34,18 +80,41 @@ impl TokenContract {
         // Verify admin authorization
         admin.require_auth();
 
+        Self::require_not_initialized(&env);
+
         // Validasi input
         if total_supply <= 0 {
             panic!("Total supply harus lebih dari 0");
         }
 
         // Simpan token info
+        env.storage().instance().set(&ADMIN, &admin);
         env.storage().instance().set(&NAME, &name);
         env.storage().instance().set(&SYMBOL, &symbol);
         env.storage().instance().set(&TOTAL, &total_supply);
 
         // Set balance admin = total supply
-        env.storage().instance().set(&BALANCE, &total_supply);
+        Self::receive_balance(&env, &admin, total_supply);
+    }
+
+    // Initialize token sebagai wrapper 1:1 dari asset lain (misal XLM lewat SAC).
+    // Supply awal 0, token hanya dicetak lewat deposit.
+    pub fn initialize_wrapped(
+        env: Env,
+        admin: Address,
+        name: String,
+        symbol: String,
+        underlying: Address,
+    ) {
+        admin.require_auth();
+
+        Self::require_not_initialized(&env);
+
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&NAME, &name);
+        env.storage().instance().set(&SYMBOL, &symbol);
+        env.storage().instance().set(&TOTAL, &0i128);
+        env.storage().instance().set(&UNDERLYING, &underlying);
     }
 
     // Get nama token
@@ -63,12 +132,57 @@ impl TokenContract {
         env.storage().instance().get(&TOTAL).unwrap()
     }
 
-    // Get balance
+    // Get balance admin
     pub fn get_balance(env: Env) -> i128 {
-        env.storage().instance().get(&BALANCE).unwrap_or(0)
+        match env.storage().instance().get::<_, Address>(&ADMIN) {
+            Some(admin) => Self::read_balance(&env, &admin),
+            None => 0,
+        }
+    }
+
+    // Get admin token
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&ADMIN).unwrap()
+    }
+
+    // Admin menyerahkan hak admin ke address lain (misal contract bridge)
+    pub fn set_admin(env: Env, new_admin: Address) {
+        let admin: Address = env.storage().instance().get(&ADMIN).unwrap();
+        admin.require_auth();
+
+        env.storage().instance().set(&ADMIN, &new_admin);
+    }
+
+    // Admin mencetak token baru ke `to`
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let admin: Address = env.storage().instance().get(&ADMIN).unwrap();
+        admin.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount harus lebih dari 0");
+        }
+
+        // Supply wrapped token harus selalu sama dengan underlying yang dikunci
+        if env.storage().instance().has(&UNDERLYING) {
+            panic!("Wrapped token hanya bisa dicetak lewat deposit");
+        }
+
+        Self::receive_balance(&env, &to, amount);
+        let total: i128 = env.storage().instance().get(&TOTAL).unwrap();
+        env.storage().instance().set(&TOTAL, &(total + amount));
+    }
+
+    // Get balance untuk address tertentu
+    pub fn balance(env: Env, id: Address) -> i128 {
+        Self::read_balance(&env, &id)
     }
 
-    // Transfer token (simplified - real token contract lebih kompleks)
+    // Get address asset yang di-wrap (None kalau bukan wrapped token)
+    pub fn get_underlying(env: Env) -> Option<Address> {
+        env.storage().instance().get(&UNDERLYING)
+    }
+
+    // Transfer token dari `from` ke `to`
     pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
         // Verify authorization
         from.require_auth();
@@ -78,18 +192,193 @@ impl TokenContract {
             panic!("Amount harus lebih dari 0");
         }
 
-        // Get current balances (simplified)
-        let balance: i128 = env.storage().instance().get(&BALANCE).unwrap_or(0);
+        Self::check_spend_limit(&env, &from, amount);
+        Self::spend_balance(&env, &from, amount);
+        Self::receive_balance(&env, &to, amount);
+    }
+
+    // Izinkan `spender` memindahkan token milik `from` sampai `amount`
+    pub fn approve(env: Env, from: Address, spender: Address, amount: i128) {
+        from.require_auth();
+
+        if amount < 0 {
+            panic!("Amount tidak boleh negatif");
+        }
+
+        env.storage().persistent().set(&DataKey::Allowance(from, spender), &amount);
+    }
+
+    // Get sisa allowance `spender` atas token milik `from`
+    pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::Allowance(from, spender)).unwrap_or(0)
+    }
+
+    // Transfer token milik `from` oleh `spender` memakai allowance
+    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount harus lebih dari 0");
+        }
+
+        Self::spend_allowance(&env, &from, &spender, amount);
+        Self::check_spend_limit(&env, &from, amount);
+        Self::spend_balance(&env, &from, amount);
+        Self::receive_balance(&env, &to, amount);
+    }
+
+    // Bakar token milik `from`
+    pub fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount harus lebih dari 0");
+        }
+
+        // Supply wrapped token harus selalu sama dengan underlying yang dikunci
+        if env.storage().instance().has(&UNDERLYING) {
+            panic!("Wrapped token hanya bisa dibakar lewat withdraw");
+        }
+
+        Self::check_spend_limit(&env, &from, amount);
+        Self::spend_balance(&env, &from, amount);
+        let total: i128 = env.storage().instance().get(&TOTAL).unwrap();
+        env.storage().instance().set(&TOTAL, &(total - amount));
+    }
+
+    // Transfer ke contract lalu panggil on_token_received di contract tersebut.
+    // Kalau callback gagal, seluruh transfer ikut dibatalkan.
+    pub fn transfer_and_call(env: Env, from: Address, to_contract: Address, amount: i128, data: Bytes) {
+        Self::transfer(env.clone(), from.clone(), to_contract.clone(), amount);
+
+        let receiver = TokenReceiverClient::new(&env, &to_contract);
+        receiver.on_token_received(&from, &amount, &data);
+    }
+
+    // Kunci asset underlying dan cetak wrapped token 1:1
+    pub fn deposit(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount harus lebih dari 0");
+        }
+
+        let underlying = token::Client::new(&env, &Self::require_underlying(&env));
+        let contract_address = env.current_contract_address();
+        underlying.transfer(&from, &contract_address, &amount);
+
+        Self::receive_balance(&env, &from, amount);
+        let total: i128 = env.storage().instance().get(&TOTAL).unwrap();
+        env.storage().instance().set(&TOTAL, &(total + amount));
+    }
+
+    // Bakar wrapped token dan lepaskan asset underlying 1:1
+    pub fn withdraw(env: Env, to: Address, amount: i128) {
+        to.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount harus lebih dari 0");
+        }
+
+        let underlying = token::Client::new(&env, &Self::require_underlying(&env));
+        let contract_address = env.current_contract_address();
+
+        Self::check_spend_limit(&env, &to, amount);
+        Self::spend_balance(&env, &to, amount);
+        let total: i128 = env.storage().instance().get(&TOTAL).unwrap();
+        env.storage().instance().set(&TOTAL, &(total - amount));
+
+        underlying.transfer(&contract_address, &to, &amount);
+    }
+
+    fn require_not_initialized(env: &Env) {
+        if env.storage().instance().has(&NAME) {
+            panic!("Token sudah diinisialisasi");
+        }
+    }
+
+    fn require_underlying(env: &Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&UNDERLYING)
+            .unwrap_or_else(|| panic!("Token bukan wrapped token"))
+    }
+
+    fn read_balance(env: &Env, id: &Address) -> i128 {
+        env.storage().persistent().get(&DataKey::Balance(id.clone())).unwrap_or(0)
+    }
+
+    fn write_balance(env: &Env, id: &Address, amount: i128) {
+        let key = DataKey::Balance(id.clone());
+        let old_amount = Self::read_balance(env, id);
+        env.storage().persistent().set(&key, &amount);
+        env.storage().persistent().extend_ttl(&key, BALANCE_TTL_THRESHOLD, BALANCE_TTL_EXTEND);
+
+        Self::update_holder_index(env, id, old_amount, amount);
+    }
+
+    fn receive_balance(env: &Env, id: &Address, amount: i128) {
+        Self::require_authorized(env, id);
+        Self::credit_balance(env, id, amount);
+    }
+
+    // Kembalikan token yang di-escrow contract (HTLC, stream) ke pengirimnya.
+    // Tidak dicek authorized: token itu memang miliknya, dan tetap tidak bisa
+    // dipindahkan selama address belum authorized lagi.
+    pub(crate) fn return_escrow(env: &Env, sender: &Address, amount: i128) {
+        Self::spend_balance(env, &env.current_contract_address(), amount);
+        Self::credit_balance(env, sender, amount);
+    }
+
+    fn credit_balance(env: &Env, id: &Address, amount: i128) {
+        let balance = Self::read_balance(env, id);
+        Self::write_balance(env, id, balance + amount);
+        Self::adjust_dividend_correction(env, id, amount);
+    }
+
+    fn spend_balance(env: &Env, id: &Address, amount: i128) {
+        Self::require_authorized(env, id);
+
+        let balance = Self::read_balance(env, id);
 
         // Check sufficient balance
         if balance < amount {
             panic!("Balance tidak cukup");
         }
 
-        // Update balance (simplified version)
-        let new_balance = balance - amount;
-        env.storage().instance().set(&BALANCE, &new_balance);
+        Self::write_balance(env, id, balance - amount);
+        Self::adjust_dividend_correction(env, id, -amount);
+    }
+
+    fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
+        if allowance < amount {
+            panic!("Allowance tidak cukup");
+        }
+
+        env.storage().persistent().set(&DataKey::Allowance(from.clone(), spender.clone()), &(allowance - amount));
+    }
+
+    // Catat `id` (lock/stream) di index milik sebuah address
+    pub(crate) fn index_add(env: &Env, key: &DataKey, id: u32) {
+        let mut ids: Vec<u32> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+        if !ids.contains(id) {
+            ids.push_back(id);
+            env.storage().persistent().set(key, &ids);
+        }
+    }
+
+    pub(crate) fn index_remove(env: &Env, key: &DataKey, id: u32) {
+        let mut ids: Vec<u32> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+        if let Some(position) = ids.first_index_of(id) {
+            ids.remove(position);
+        }
+        if ids.is_empty() {
+            env.storage().persistent().remove(key);
+        } else {
+            env.storage().persistent().set(key, &ids);
+        }
     }
 }
 
-mod test;
\ No newline at end of file
+mod test;
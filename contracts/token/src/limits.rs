@@ -0,0 +1,104 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+use crate::{DataKey, TokenContract, TokenContractArgs, TokenContractClient, ADMIN};
+
+// Panjang window limit (24 jam, rolling)
+pub const LIMIT_WINDOW: u64 = 86_400;
+
+// Batas outflow per window. Limit yang diset admin (`managed`) tidak bisa
+// diubah oleh pemilik akun.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendLimit {
+    pub limit: i128,
+    pub managed: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Outflow {
+    pub timestamp: u64,
+    pub amount: i128,
+}
+
+#[contractimpl]
+impl TokenContract {
+
+    // Pemilik akun atau admin mengatur limit outflow per 24 jam untuk `id`.
+    // `None` menghapus limit.
+    pub fn set_spend_limit(env: Env, caller: Address, id: Address, limit: Option<i128>) {
+        caller.require_auth();
+
+        let admin: Address = env.storage().instance().get(&ADMIN).unwrap();
+
+        let managed = if caller == admin {
+            true
+        } else if caller == id {
+            if Self::get_spend_limit(env.clone(), id.clone()).is_some_and(|current| current.managed) {
+                panic!("Limit dikelola admin");
+            }
+            false
+        } else {
+            panic!("Tidak berhak mengatur limit");
+        };
+
+        match limit {
+            Some(limit) => {
+                if limit < 0 {
+                    panic!("Limit tidak boleh negatif");
+                }
+                env.storage().persistent().set(&DataKey::SpendLimit(id), &SpendLimit { limit, managed });
+            }
+            None => {
+                env.storage().persistent().remove(&DataKey::SpendLimit(id.clone()));
+                env.storage().persistent().remove(&DataKey::Outflows(id));
+            }
+        }
+    }
+
+    // Get limit yang berlaku untuk `id`
+    pub fn get_spend_limit(env: Env, id: Address) -> Option<SpendLimit> {
+        env.storage().persistent().get(&DataKey::SpendLimit(id))
+    }
+
+    // Get sisa yang masih bisa dikirim dalam 24 jam terakhir (None = tanpa limit)
+    pub fn remaining_limit(env: Env, id: Address) -> Option<i128> {
+        let limit = Self::get_spend_limit(env.clone(), id.clone())?;
+        let spent: i128 = Self::recent_outflows(&env, &id).iter().map(|o| o.amount).sum();
+        Some((limit.limit - spent).max(0))
+    }
+
+    // Dipanggil di setiap outflow yang diminta pemilik akun
+    pub(crate) fn check_spend_limit(env: &Env, id: &Address, amount: i128) {
+        let limit = match Self::get_spend_limit(env.clone(), id.clone()) {
+            Some(limit) => limit,
+            None => return,
+        };
+
+        let mut recent = Self::recent_outflows(env, id);
+        let spent: i128 = recent.iter().map(|o| o.amount).sum();
+        if spent + amount > limit.limit {
+            panic!("Limit harian terlampaui");
+        }
+
+        recent.push_back(Outflow { timestamp: env.ledger().timestamp(), amount });
+
+        env.storage().persistent().set(&DataKey::Outflows(id.clone()), &recent);
+    }
+
+    // Outflow dalam window 24 jam terakhir, yang lebih lama dibuang
+    fn recent_outflows(env: &Env, id: &Address) -> Vec<Outflow> {
+        let outflows: Vec<Outflow> = env.storage().persistent()
+            .get(&DataKey::Outflows(id.clone()))
+            .unwrap_or(Vec::new(env));
+        let now = env.ledger().timestamp();
+
+        let mut recent = Vec::new(env);
+        for outflow in outflows.iter() {
+            if outflow.timestamp + LIMIT_WINDOW > now {
+                recent.push_back(outflow);
+            }
+        }
+        recent
+    }
+}
@@ -0,0 +1,159 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::{DataKey, TokenContract, TokenContractArgs, TokenContractClient};
+
+// Storage keys
+const STREAM_ID: Symbol = symbol_short!("STREAM_ID");
+
+// Pembayaran kontinu: `rate` token per detik mengalir dari sender ke
+// recipient antara `start` dan `stop`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stream {
+    pub sender: Address,
+    pub recipient: Address,
+    pub rate: i128,
+    pub start: u64,
+    pub stop: u64,
+    pub deposit: i128,
+    pub withdrawn: i128,
+}
+
+#[contractimpl]
+impl TokenContract {
+
+    // Kunci rate * (stop - start) token dari sender ke dalam stream baru
+    pub fn create_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        rate: i128,
+        start: u64,
+        stop: u64,
+    ) -> u32 {
+        sender.require_auth();
+
+        if rate <= 0 {
+            panic!("Rate harus lebih dari 0");
+        }
+        if start < env.ledger().timestamp() {
+            panic!("Start tidak boleh di masa lalu");
+        }
+        if stop <= start {
+            panic!("Stop harus setelah start");
+        }
+
+        let deposit = rate * (stop - start) as i128;
+        Self::check_spend_limit(&env, &sender, deposit);
+        Self::spend_balance(&env, &sender, deposit);
+        Self::receive_balance(&env, &env.current_contract_address(), deposit);
+
+        let id: u32 = env.storage().instance().get(&STREAM_ID).unwrap_or(0);
+        Self::index_add(&env, &DataKey::StreamsOf(sender.clone()), id);
+        Self::index_add(&env, &DataKey::StreamsOf(recipient.clone()), id);
+        env.storage().persistent().set(
+            &DataKey::Stream(id),
+            &Stream { sender, recipient, rate, start, stop, deposit, withdrawn: 0 },
+        );
+        env.storage().instance().set(&STREAM_ID, &(id + 1));
+
+        id
+    }
+
+    // Recipient menarik semua token yang sudah mengalir, return jumlahnya
+    pub fn withdraw_stream(env: Env, id: u32) -> i128 {
+        let mut stream = Self::get_stream(env.clone(), id).unwrap_or_else(|| panic!("Stream tidak ditemukan"));
+        stream.recipient.require_auth();
+
+        let amount = Self::streamed(&env, &stream) - stream.withdrawn;
+        if amount <= 0 {
+            return 0;
+        }
+
+        stream.withdrawn += amount;
+        if stream.withdrawn == stream.deposit {
+            Self::remove_stream(&env, id, &stream);
+        } else {
+            env.storage().persistent().set(&DataKey::Stream(id), &stream);
+        }
+
+        Self::spend_balance(&env, &env.current_contract_address(), amount);
+        Self::receive_balance(&env, &stream.recipient, amount);
+
+        amount
+    }
+
+    // Sender atau recipient menghentikan stream; bagian yang sudah mengalir
+    // ke recipient, sisanya kembali ke sender. Kalau recipient sedang tidak
+    // authorized, bagiannya tetap di stream sampai bisa ditarik lewat withdraw_stream.
+    pub fn cancel_stream(env: Env, caller: Address, id: u32) {
+        caller.require_auth();
+
+        let mut stream = Self::get_stream(env.clone(), id).unwrap_or_else(|| panic!("Stream tidak ditemukan"));
+        if caller != stream.sender && caller != stream.recipient {
+            panic!("Hanya sender atau recipient yang bisa cancel");
+        }
+
+        let streamed = Self::streamed(&env, &stream);
+        let to_recipient = streamed - stream.withdrawn;
+        let to_sender = stream.deposit - streamed;
+
+        if to_recipient > 0 && !Self::authorized(env.clone(), stream.recipient.clone()) {
+            // Hentikan aliran di sini; deposit tinggal bagian recipient
+            stream.stop = stream.stop.min(env.ledger().timestamp());
+            stream.deposit = streamed;
+            env.storage().persistent().set(&DataKey::Stream(id), &stream);
+        } else {
+            Self::remove_stream(&env, id, &stream);
+            if to_recipient > 0 {
+                Self::spend_balance(&env, &env.current_contract_address(), to_recipient);
+                Self::receive_balance(&env, &stream.recipient, to_recipient);
+            }
+        }
+
+        if to_sender > 0 {
+            Self::return_escrow(&env, &stream.sender, to_sender);
+        }
+    }
+
+    // Get detail stream yang masih aktif
+    pub fn get_stream(env: Env, id: u32) -> Option<Stream> {
+        env.storage().persistent().get(&DataKey::Stream(id))
+    }
+
+    // Total token yang sudah mengalir sampai timestamp ledger sekarang
+    pub fn streamed_amount(env: Env, id: u32) -> i128 {
+        match Self::get_stream(env.clone(), id) {
+            Some(stream) => Self::streamed(&env, &stream),
+            None => 0,
+        }
+    }
+
+    // Token yang sudah mengalir tapi belum ditarik recipient
+    pub fn withdrawable_amount(env: Env, id: u32) -> i128 {
+        match Self::get_stream(env.clone(), id) {
+            Some(stream) => Self::streamed(&env, &stream) - stream.withdrawn,
+            None => 0,
+        }
+    }
+
+    // Get id semua stream aktif di mana `id` adalah sender atau recipient
+    pub fn streams_of(env: Env, id: Address) -> Vec<u32> {
+        env.storage().persistent().get(&DataKey::StreamsOf(id)).unwrap_or(Vec::new(&env))
+    }
+
+    fn streamed(env: &Env, stream: &Stream) -> i128 {
+        let now = env.ledger().timestamp();
+        if now <= stream.start {
+            return 0;
+        }
+        let elapsed = now.min(stream.stop) - stream.start;
+        stream.rate * elapsed as i128
+    }
+
+    fn remove_stream(env: &Env, id: u32, stream: &Stream) {
+        env.storage().persistent().remove(&DataKey::Stream(id));
+        Self::index_remove(env, &DataKey::StreamsOf(stream.sender.clone()), id);
+        Self::index_remove(env, &DataKey::StreamsOf(stream.recipient.clone()), id);
+    }
+}
@@ -1,7 +1,14 @@
 #![cfg(test)]
+extern crate std;
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{
+    contract, contractimpl,
+    events::Event,
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, Bytes, BytesN, Env, String, Vec,
+};
 
 #[test]
 fn test_initialize_token() {
@@ -92,4 +99,1243 @@ fn test_transfer() {
 
     // Balance should be reduced
     assert_eq!(client.get_balance(), supply - transfer_amount);
-}
\ No newline at end of file
+    assert_eq!(client.balance(&user), transfer_amount);
+}
+
+// Helper function to create a Stellar asset (wrapped underlying, dividend token)
+fn create_asset<'a>(env: &Env, admin: &Address) -> token::StellarAssetClient<'a> {
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    token::StellarAssetClient::new(env, &asset.address())
+}
+
+#[test]
+fn test_wrapped_deposit_withdraw() {
+    let env = Env::default();
+    let contract_id = env.register(TokenContract, ());
+    let client = TokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let underlying = create_asset(&env, &admin);
+    let underlying_token = token::Client::new(&env, &underlying.address);
+    underlying.mint(&alice, &1_000_000);
+
+    let name = String::from_str(&env, "Wrapped XLM");
+    let symbol = String::from_str(&env, "wXLM");
+    client.initialize_wrapped(&admin, &name, &symbol, &underlying.address);
+
+    assert_eq!(client.get_underlying(), Some(underlying.address.clone()));
+    assert_eq!(client.get_total_supply(), 0);
+
+    // Deposit locks the underlying and mints wrapped tokens
+    client.deposit(&alice, &600_000);
+    assert_eq!(client.balance(&alice), 600_000);
+    assert_eq!(underlying_token.balance(&alice), 400_000);
+    assert_eq!(client.get_total_supply(), underlying_token.balance(&contract_id));
+
+    // Wrapped tokens move freely without touching the underlying
+    client.transfer(&alice, &bob, &250_000);
+    assert_eq!(client.balance(&bob), 250_000);
+    assert_eq!(client.get_total_supply(), underlying_token.balance(&contract_id));
+
+    // Withdraw burns wrapped tokens and releases the underlying
+    client.withdraw(&bob, &250_000);
+    assert_eq!(client.balance(&bob), 0);
+    assert_eq!(underlying_token.balance(&bob), 250_000);
+    assert_eq!(client.get_total_supply(), 350_000);
+    assert_eq!(client.get_total_supply(), underlying_token.balance(&contract_id));
+}
+
+#[test]
+#[should_panic(expected = "Balance tidak cukup")]
+fn test_wrapped_withdraw_more_than_balance() {
+    let env = Env::default();
+    let contract_id = env.register(TokenContract, ());
+    let client = TokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let underlying = create_asset(&env, &admin);
+    underlying.mint(&user, &1_000);
+
+    let name = String::from_str(&env, "Wrapped XLM");
+    let symbol = String::from_str(&env, "wXLM");
+    client.initialize_wrapped(&admin, &name, &symbol, &underlying.address);
+
+    client.deposit(&user, &1_000);
+
+    // Should panic
+    client.withdraw(&user, &1_001);
+}
+
+#[test]
+#[should_panic(expected = "Token bukan wrapped token")]
+fn test_deposit_on_plain_token() {
+    let env = Env::default();
+    let contract_id = env.register(TokenContract, ());
+    let client = TokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    env.mock_all_auths();
+
+    let name = String::from_str(&env, "Test Token");
+    let symbol = String::from_str(&env, "TST");
+    client.initialize(&admin, &name, &symbol, &1_000_000);
+
+    // Should panic
+    client.deposit(&admin, &100);
+}
+// Receiver contract that records the last callback it got
+#[contract]
+pub struct MockReceiver;
+
+#[contractimpl]
+impl MockReceiver {
+    pub fn on_token_received(env: Env, from: Address, amount: i128, data: Bytes) {
+        env.storage().instance().set(&symbol_short!("from"), &from);
+        env.storage().instance().set(&symbol_short!("amount"), &amount);
+        env.storage().instance().set(&symbol_short!("data"), &data);
+    }
+
+    pub fn last_amount(env: Env) -> i128 {
+        env.storage().instance().get(&symbol_short!("amount")).unwrap_or(0)
+    }
+
+    pub fn last_from(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("from"))
+    }
+}
+
+// Receiver contract that always rejects incoming tokens
+#[contract]
+pub struct RejectingReceiver;
+
+#[contractimpl]
+impl RejectingReceiver {
+    pub fn on_token_received(_env: Env, _from: Address, _amount: i128, _data: Bytes) {
+        panic!("Receiver menolak token");
+    }
+}
+
+#[test]
+fn test_transfer_and_call() {
+    let env = Env::default();
+    let contract_id = env.register(TokenContract, ());
+    let client = TokenContractClient::new(&env, &contract_id);
+    let receiver_id = env.register(MockReceiver, ());
+    let receiver = MockReceiverClient::new(&env, &receiver_id);
+
+    let admin = Address::generate(&env);
+    env.mock_all_auths();
+
+    let name = String::from_str(&env, "Test Token");
+    let symbol = String::from_str(&env, "TST");
+    client.initialize(&admin, &name, &symbol, &1_000_000);
+
+    let data = Bytes::from_slice(&env, b"memo");
+    client.transfer_and_call(&admin, &receiver_id, &10_000, &data);
+
+    // Tokens arrived and the receiver was notified
+    assert_eq!(client.balance(&receiver_id), 10_000);
+    assert_eq!(receiver.last_amount(), 10_000);
+    assert_eq!(receiver.last_from(), Some(admin));
+}
+
+#[test]
+fn test_transfer_and_call_reverts_when_receiver_fails() {
+    let env = Env::default();
+    let contract_id = env.register(TokenContract, ());
+    let client = TokenContractClient::new(&env, &contract_id);
+    let receiver_id = env.register(RejectingReceiver, ());
+
+    let admin = Address::generate(&env);
+    env.mock_all_auths();
+
+    let name = String::from_str(&env, "Test Token");
+    let symbol = String::from_str(&env, "TST");
+    client.initialize(&admin, &name, &symbol, &1_000_000);
+
+    let data = Bytes::new(&env);
+    let result = client.try_transfer_and_call(&admin, &receiver_id, &10_000, &data);
+    assert!(result.is_err());
+
+    // Transfer was rolled back together with the callback
+    assert_eq!(client.balance(&admin), 1_000_000);
+    assert_eq!(client.balance(&receiver_id), 0);
+}
+
+// Helper function to set up a plain token with the whole supply on `admin`
+fn setup_token<'a>(env: &Env, admin: &Address, supply: i128) -> TokenContractClient<'a> {
+    let contract_id = env.register(TokenContract, ());
+    let client = TokenContractClient::new(env, &contract_id);
+    let name = String::from_str(env, "Test Token");
+    let symbol = String::from_str(env, "TST");
+    client.initialize(admin, &name, &symbol, &supply);
+    client
+}
+
+#[test]
+fn test_htlc_lock_and_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let client = setup_token(&env, &sender, 1_000_000);
+
+    let preimage = Bytes::from_slice(&env, b"rahasia");
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage).into();
+    let timelock = env.ledger().timestamp() + 3600;
+
+    let id = client.lock(&sender, &recipient, &40_000, &hashlock, &timelock);
+
+    // Tokens are escrowed by the contract
+    assert_eq!(client.balance(&sender), 960_000);
+    assert_eq!(client.balance(&client.address), 40_000);
+    assert_eq!(client.locks_of(&sender).len(), 1);
+    assert_eq!(client.locks_of(&recipient).get(0), Some(id));
+    assert_eq!(client.get_lock(&id).unwrap().amount, 40_000);
+
+    client.claim(&id, &preimage);
+
+    assert_eq!(client.balance(&recipient), 40_000);
+    assert_eq!(client.balance(&client.address), 0);
+    assert_eq!(client.get_lock(&id), None);
+    assert_eq!(client.locks_of(&recipient).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Preimage tidak cocok")]
+fn test_htlc_claim_wrong_preimage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let client = setup_token(&env, &sender, 1_000_000);
+
+    let preimage = Bytes::from_slice(&env, b"rahasia");
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage).into();
+    let timelock = env.ledger().timestamp() + 3600;
+
+    let id = client.lock(&sender, &recipient, &40_000, &hashlock, &timelock);
+
+    // Should panic
+    client.claim(&id, &Bytes::from_slice(&env, b"salah"));
+}
+
+#[test]
+#[should_panic(expected = "Timelock sudah lewat")]
+fn test_htlc_claim_after_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let client = setup_token(&env, &sender, 1_000_000);
+
+    let preimage = Bytes::from_slice(&env, b"rahasia");
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage).into();
+    let timelock = env.ledger().timestamp() + 3600;
+
+    let id = client.lock(&sender, &recipient, &40_000, &hashlock, &timelock);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = timelock + 1;
+    });
+
+    // Should panic
+    client.claim(&id, &preimage);
+}
+
+#[test]
+fn test_htlc_reclaim_after_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let client = setup_token(&env, &sender, 1_000_000);
+
+    let hashlock = BytesN::from_array(&env, &[7u8; 32]);
+    let timelock = env.ledger().timestamp() + 3600;
+
+    let id = client.lock(&sender, &recipient, &40_000, &hashlock, &timelock);
+
+    // Not yet refundable
+    assert!(client.try_reclaim(&id).is_err());
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = timelock + 1;
+    });
+
+    client.reclaim(&id);
+
+    assert_eq!(client.balance(&sender), 1_000_000);
+    assert_eq!(client.balance(&client.address), 0);
+    assert_eq!(client.locks_of(&sender).len(), 0);
+}
+
+#[test]
+fn test_stream_withdraw_and_complete() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let client = setup_token(&env, &sender, 1_000_000);
+
+    let start = env.ledger().timestamp() + 100;
+    let stop = start + 1_000;
+    let id = client.create_stream(&sender, &recipient, &10, &start, &stop);
+
+    // Deposit of rate * duration is locked up front
+    assert_eq!(client.balance(&sender), 990_000);
+    assert_eq!(client.balance(&client.address), 10_000);
+    assert_eq!(client.streamed_amount(&id), 0);
+
+    // Halfway through the stream
+    env.ledger().with_mut(|li| {
+        li.timestamp = start + 500;
+    });
+    assert_eq!(client.streamed_amount(&id), 5_000);
+    assert_eq!(client.withdrawable_amount(&id), 5_000);
+
+    assert_eq!(client.withdraw_stream(&id), 5_000);
+    assert_eq!(client.balance(&recipient), 5_000);
+    assert_eq!(client.withdrawable_amount(&id), 0);
+
+    // Past the stop time everything is withdrawable and the stream closes
+    env.ledger().with_mut(|li| {
+        li.timestamp = stop + 50;
+    });
+    assert_eq!(client.withdrawable_amount(&id), 5_000);
+    assert_eq!(client.withdraw_stream(&id), 5_000);
+    assert_eq!(client.balance(&recipient), 10_000);
+    assert_eq!(client.get_stream(&id), None);
+    assert_eq!(client.streams_of(&sender).len(), 0);
+}
+
+#[test]
+fn test_stream_cancel_splits_pro_rata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let client = setup_token(&env, &sender, 1_000_000);
+
+    let start = env.ledger().timestamp();
+    let stop = start + 1_000;
+    let id = client.create_stream(&sender, &recipient, &10, &start, &stop);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = start + 300;
+    });
+    client.withdraw_stream(&id);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = start + 400;
+    });
+    client.cancel_stream(&recipient, &id);
+
+    // Recipient got 400s worth, sender got back the unstreamed 600s
+    assert_eq!(client.balance(&recipient), 4_000);
+    assert_eq!(client.balance(&sender), 996_000);
+    assert_eq!(client.balance(&client.address), 0);
+    assert_eq!(client.get_stream(&id), None);
+}
+
+#[test]
+#[should_panic(expected = "Hanya sender atau recipient yang bisa cancel")]
+fn test_stream_cancel_by_third_party() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let client = setup_token(&env, &sender, 1_000_000);
+
+    let start = env.ledger().timestamp();
+    let id = client.create_stream(&sender, &recipient, &10, &start, &(start + 1_000));
+
+    // Should panic
+    client.cancel_stream(&stranger, &id);
+}
+
+#[test]
+fn test_dividends_pro_rata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let late_holder = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    let revenue = create_asset(&env, &admin);
+    let revenue_token = token::Client::new(&env, &revenue.address);
+    revenue.mint(&admin, &100_000);
+    client.set_dividend_token(&revenue.address);
+
+    // admin 75%, holder 25%
+    client.transfer(&admin, &holder, &250_000);
+
+    client.deposit_dividends(&admin, &40_000);
+    assert_eq!(client.pending_dividends(&admin), 30_000);
+    assert_eq!(client.pending_dividends(&holder), 10_000);
+
+    // Moving tokens after a deposit does not move already accrued dividends
+    client.transfer(&holder, &late_holder, &250_000);
+    assert_eq!(client.pending_dividends(&holder), 10_000);
+    assert_eq!(client.pending_dividends(&late_holder), 0);
+
+    // The next deposit follows the new balances
+    client.deposit_dividends(&admin, &20_000);
+    assert_eq!(client.pending_dividends(&admin), 45_000);
+    assert_eq!(client.pending_dividends(&holder), 10_000);
+    assert_eq!(client.pending_dividends(&late_holder), 5_000);
+
+    assert_eq!(client.claim_dividends(&holder), 10_000);
+    assert_eq!(revenue_token.balance(&holder), 10_000);
+    assert_eq!(client.pending_dividends(&holder), 0);
+    assert_eq!(client.claim_dividends(&holder), 0);
+}
+
+#[test]
+fn test_dividends_skip_escrowed_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    let revenue = create_asset(&env, &admin);
+    revenue.mint(&admin, &100_000);
+    client.set_dividend_token(&revenue.address);
+
+    // Half the supply sits in an HTLC escrow held by the contract itself
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = env.ledger().timestamp() + 3600;
+    client.lock(&admin, &recipient, &500_000, &hashlock, &timelock);
+
+    client.deposit_dividends(&admin, &10_000);
+    assert_eq!(client.pending_dividends(&admin), 10_000);
+    assert_eq!(client.pending_dividends(&client.address), 0);
+}
+
+#[test]
+fn test_small_dividend_deposits_carry_remainder() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let client = setup_token(&env, &admin, 10_000_000_000_000);
+
+    let revenue = create_asset(&env, &admin);
+    revenue.mint(&admin, &100);
+    client.set_dividend_token(&revenue.address);
+
+    // 1 * 1e12 / 1e13 rounds to 0 per share, so the deposit is carried over
+    client.deposit_dividends(&admin, &1);
+    assert_eq!(client.pending_dividends(&admin), 0);
+
+    for _ in 0..9 {
+        client.deposit_dividends(&admin, &1);
+    }
+    assert_eq!(client.pending_dividends(&admin), 10);
+    assert_eq!(client.claim_dividends(&admin), 10);
+}
+
+#[test]
+#[should_panic(expected = "Dividend token belum diset")]
+fn test_deposit_dividends_without_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    // Should panic
+    client.deposit_dividends(&admin, &10_000);
+}
+
+#[test]
+fn test_holder_index_and_pagination() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    assert_eq!(client.holder_count(), 1);
+
+    client.transfer(&admin, &alice, &100);
+    client.transfer(&admin, &bob, &200);
+    assert_eq!(client.holder_count(), 3);
+    assert_eq!(client.holders(&0, &2).len(), 2);
+    assert_eq!(client.holders(&2, &10).len(), 1);
+    assert_eq!(client.holders(&5, &10).len(), 0);
+
+    // Alice leaves the index once her balance hits zero
+    client.transfer(&alice, &bob, &100);
+    assert_eq!(client.holder_count(), 2);
+    let holders = client.holders(&0, &10);
+    assert!(holders.contains(&admin));
+    assert!(holders.contains(&bob));
+    assert!(!holders.contains(&alice));
+
+    // Escrow held by the contract itself is not a holder
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = env.ledger().timestamp() + 3600;
+    client.lock(&admin, &bob, &1_000, &hashlock, &timelock);
+    assert_eq!(client.holder_count(), 2);
+}
+
+#[test]
+fn test_account_state_in_persistent_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    client.transfer(&admin, &alice, &100);
+    client.approve(&alice, &admin, &50);
+
+    // Each account gets its own entries instead of a shared instance map
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        assert_eq!(storage.get::<_, i128>(&DataKey::Balance(alice.clone())), Some(100));
+        assert_eq!(storage.get::<_, i128>(&DataKey::Allowance(alice.clone(), admin.clone())), Some(50));
+        assert_eq!(storage.get::<_, u32>(&DataKey::HolderIndex(alice.clone())), Some(1));
+    });
+
+    // Leaving the holder index frees the account's index entries
+    client.transfer(&alice, &admin, &100);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::HolderIndex(alice.clone())));
+        assert!(!env.storage().persistent().has(&DataKey::Holder(1)));
+    });
+}
+
+#[test]
+fn test_top_holders_bounded_and_sorted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    // More holders than the top list can hold, balances 1_000 .. 15_000
+    let mut users = Vec::new(&env);
+    for i in 1..=15i128 {
+        let user = Address::generate(&env);
+        client.transfer(&admin, &user, &(i * 1_000));
+        users.push_back(user);
+    }
+
+    let top = client.top_holders(&100);
+    assert!(top.complete);
+    let top = top.holders;
+    assert_eq!(top.len(), TOP_HOLDERS_LIMIT);
+    assert_eq!(top.get(0).unwrap().holder, admin);
+    assert_eq!(top.get(1).unwrap().balance, 15_000);
+    assert_eq!(top.get(9).unwrap().balance, 7_000);
+    assert_eq!(client.top_holders(&3).holders.len(), 3);
+
+    // The 15_000 holder drops out; the best holder outside the list takes the slot
+    let biggest = users.get(14).unwrap();
+    client.transfer(&biggest, &admin, &14_500);
+
+    let top = client.top_holders(&100);
+    assert!(top.complete);
+    let top = top.holders;
+    assert_eq!(top.len(), TOP_HOLDERS_LIMIT);
+    assert_eq!(top.get(1).unwrap().balance, 14_000);
+    assert_eq!(top.get(9).unwrap().balance, 6_000);
+    for entry in top.iter() {
+        assert!(entry.holder != biggest);
+    }
+}
+
+#[test]
+fn test_top_holders_only_reports_known_ranks() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    // More holders than candidates, balances 1_000 .. 25_000
+    let mut users = Vec::new(&env);
+    for i in 1..=25i128 {
+        let user = Address::generate(&env);
+        client.transfer(&admin, &user, &(i * 1_000));
+        users.push_back(user);
+    }
+
+    // The twelve biggest users drop to 500; holders below the candidate
+    // list are not scanned, so only the ranks still known are reported and
+    // the list is flagged as incomplete
+    for i in 13..25u32 {
+        let user = users.get(i).unwrap();
+        client.transfer(&user, &admin, &(client.balance(&user) - 500));
+    }
+
+    let top = client.top_holders(&100);
+    assert!(!top.complete);
+    assert!(client.top_holders(&8).complete);
+    let top = top.holders;
+    assert_eq!(top.len(), 8);
+    assert_eq!(top.get(0).unwrap().holder, admin);
+    assert_eq!(top.get(1).unwrap().balance, 13_000);
+    assert_eq!(top.get(7).unwrap().balance, 7_000);
+
+    // A holder from outside the list moving up takes its rank again
+    let user = users.get(5).unwrap();
+    client.transfer(&admin, &user, &1);
+
+    let top = client.top_holders(&100);
+    assert!(!top.complete);
+    let top = top.holders;
+    assert_eq!(top.len(), 9);
+    assert_eq!(top.get(8).unwrap().holder, user);
+    assert_eq!(top.get(8).unwrap().balance, 6_001);
+}
+
+#[test]
+fn test_flash_mint_with_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let initiator = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+    let receiver_id = env.register(flash_receiver::FlashReceiver, ());
+    let receiver = flash_receiver::FlashReceiverClient::new(&env, &receiver_id);
+    receiver.initialize(&initiator);
+
+    client.set_flash_fee(&50); // 0.5%
+    assert_eq!(client.flash_fee(&100_000), 500);
+
+    // Receiver needs its own tokens to cover the fee, and approves the
+    // repayment up front since it cannot call the token during exec_op
+    client.transfer(&admin, &receiver_id, &1_000);
+    receiver.approve_repayment(&client.address, &100_500);
+
+    client.flash_mint(&initiator, &receiver_id, &100_000, &Bytes::new(&env));
+
+    // Only the initiator signed for the flash mint
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, initiator);
+
+    assert_eq!(receiver.get_calls(), 1);
+    assert_eq!(receiver.get_last_initiator(), Some(initiator));
+    assert_eq!(receiver.get_last_amount(), 100_000);
+    assert_eq!(receiver.get_last_fee(), 500);
+
+    // Minted amount was burned again and the fee went to the admin
+    assert_eq!(client.get_total_supply(), 1_000_000);
+    assert_eq!(client.balance(&receiver_id), 500);
+    assert_eq!(client.balance(&admin), 999_500);
+    assert_eq!(client.allowance(&receiver_id, &client.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Allowance tidak cukup")]
+fn test_flash_mint_requires_approved_repayment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let initiator = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+    let receiver_id = env.register(flash_receiver::FlashReceiver, ());
+    flash_receiver::FlashReceiverClient::new(&env, &receiver_id).initialize(&initiator);
+
+    // The token never takes from the receiver without its approval
+    client.transfer(&admin, &receiver_id, &1_000);
+
+    // Should panic
+    client.flash_mint(&initiator, &receiver_id, &100_000, &Bytes::new(&env));
+}
+
+#[test]
+fn test_flash_mint_reverts_without_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let initiator = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+    let receiver_id = env.register(flash_receiver::FlashReceiver, ());
+    let receiver = flash_receiver::FlashReceiverClient::new(&env, &receiver_id);
+    receiver.initialize(&initiator);
+
+    client.set_flash_fee(&50);
+    receiver.approve_repayment(&client.address, &100_500);
+
+    // Receiver holds nothing to pay the fee with
+    let result = client.try_flash_mint(&initiator, &receiver_id, &100_000, &Bytes::new(&env));
+    assert!(result.is_err());
+    assert_eq!(client.get_total_supply(), 1_000_000);
+    assert_eq!(client.balance(&receiver_id), 0);
+}
+
+#[test]
+fn test_flash_mint_rejects_stranger() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+    let receiver_id = env.register(flash_receiver::FlashReceiver, ());
+    let receiver = flash_receiver::FlashReceiverClient::new(&env, &receiver_id);
+    receiver.initialize(&owner);
+
+    client.set_flash_fee(&50);
+    client.transfer(&admin, &receiver_id, &1_000);
+    receiver.approve_repayment(&client.address, &100_500);
+
+    // Only the owner signs for the receiver's admin calls
+    assert_eq!(env.auths()[0].0, owner);
+
+    // A flash mint the owner did not start cannot spend the approval on fees
+    let result = client.try_flash_mint(&stranger, &receiver_id, &100_000, &Bytes::new(&env));
+    assert!(result.is_err());
+    assert_eq!(client.balance(&receiver_id), 1_000);
+    assert_eq!(client.allowance(&receiver_id, &client.address), 100_500);
+    assert_eq!(receiver.get_calls(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Flash mint ditolak receiver")]
+fn test_flash_mint_rejected_by_receiver() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let initiator = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+    let receiver_id = env.register(flash_receiver::FlashReceiver, ());
+    let receiver = flash_receiver::FlashReceiverClient::new(&env, &receiver_id);
+    receiver.initialize(&initiator);
+
+    receiver.set_accept(&false);
+
+    // Should panic
+    client.flash_mint(&initiator, &receiver_id, &100_000, &Bytes::new(&env));
+}
+
+#[test]
+#[should_panic(expected = "Fee flash mint terlalu besar")]
+fn test_flash_fee_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    // Should panic
+    client.set_flash_fee(&(MAX_FLASH_FEE_BPS + 1));
+}
+
+#[test]
+fn test_auth_required_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let kyc_user = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    // Mode is off by default, everyone may receive
+    assert!(!client.get_auth_required());
+    assert!(client.authorized(&kyc_user));
+
+    client.set_auth_required(&true);
+    assert!(!client.authorized(&kyc_user));
+    assert!(client.authorized(&admin));
+    assert!(client.try_transfer(&admin, &kyc_user, &100).is_err());
+
+    client.set_authorized(&kyc_user, &true);
+    let event = AuthorizedChanged { id: kyc_user.clone(), authorized: true };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+
+    client.transfer(&admin, &kyc_user, &100);
+    assert_eq!(client.balance(&kyc_user), 100);
+
+    // Revoking blocks further incoming tokens
+    client.set_authorized(&kyc_user, &false);
+    assert!(!client.authorized(&kyc_user));
+    assert!(client.try_transfer(&admin, &kyc_user, &100).is_err());
+    assert_eq!(client.balance(&kyc_user), 100);
+
+    // ...and every outgoing path for the tokens it still holds
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = env.ledger().timestamp() + 3600;
+    let now = env.ledger().timestamp();
+    client.approve(&kyc_user, &admin, &100);
+    assert!(client.try_transfer(&kyc_user, &admin, &50).is_err());
+    assert!(client.try_transfer_from(&admin, &kyc_user, &admin, &50).is_err());
+    assert!(client.try_burn(&kyc_user, &50).is_err());
+    assert!(client.try_lock(&kyc_user, &admin, &50, &hashlock, &timelock).is_err());
+    assert!(client.try_create_stream(&kyc_user, &admin, &1, &now, &(now + 50)).is_err());
+    assert_eq!(client.balance(&kyc_user), 100);
+}
+
+#[test]
+fn test_auth_required_escrow_payouts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    client.set_auth_required(&true);
+    client.set_authorized(&sender, &true);
+    client.set_authorized(&recipient, &true);
+    client.transfer(&admin, &sender, &10_000);
+
+    let start = env.ledger().timestamp();
+    let stream_id = client.create_stream(&sender, &recipient, &10, &start, &(start + 500));
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let lock_id = client.lock(&sender, &recipient, &1_000, &hashlock, &(start + 100));
+
+    // Both parties lose authorization while their tokens are in escrow
+    client.set_authorized(&sender, &false);
+    client.set_authorized(&recipient, &false);
+    env.ledger().with_mut(|li| li.timestamp = start + 200);
+
+    // Escrow still goes back to the sender, where it stays frozen
+    client.reclaim(&lock_id);
+    client.cancel_stream(&sender, &stream_id);
+    assert_eq!(client.balance(&sender), 10_000 - 2_000);
+    assert!(client.try_transfer(&sender, &admin, &1).is_err());
+
+    // The recipient's streamed share waits in the stream until re-authorized
+    let stream = client.get_stream(&stream_id).unwrap();
+    assert_eq!(stream.deposit, 2_000);
+    assert_eq!(client.withdrawable_amount(&stream_id), 2_000);
+    assert!(client.try_withdraw_stream(&stream_id).is_err());
+
+    client.set_authorized(&recipient, &true);
+    env.ledger().with_mut(|li| li.timestamp = start + 400);
+    assert_eq!(client.withdraw_stream(&stream_id), 2_000);
+    assert_eq!(client.balance(&recipient), 2_000);
+    assert_eq!(client.get_stream(&stream_id), None);
+    assert_eq!(client.balance(&client.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Address belum authorized")]
+fn test_auth_required_blocks_wrapped_deposit() {
+    let env = Env::default();
+    let contract_id = env.register(TokenContract, ());
+    let client = TokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    env.mock_all_auths();
+
+    let underlying = create_asset(&env, &admin);
+    underlying.mint(&user, &1_000);
+
+    let name = String::from_str(&env, "Wrapped XLM");
+    let symbol = String::from_str(&env, "wXLM");
+    client.initialize_wrapped(&admin, &name, &symbol, &underlying.address);
+    client.set_auth_required(&true);
+
+    // Should panic: minting wrapped tokens is also balance-increasing
+    client.deposit(&user, &1_000);
+}
+
+#[test]
+fn test_mint_and_set_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    client.mint(&user, &5_000);
+    assert_eq!(client.balance(&user), 5_000);
+    assert_eq!(client.get_total_supply(), 1_005_000);
+
+    client.set_admin(&new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+}
+
+#[test]
+#[should_panic(expected = "Wrapped token hanya bisa dicetak lewat deposit")]
+fn test_mint_on_wrapped_token() {
+    let env = Env::default();
+    let contract_id = env.register(TokenContract, ());
+    let client = TokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    env.mock_all_auths();
+
+    let underlying = create_asset(&env, &admin);
+    let name = String::from_str(&env, "Wrapped XLM");
+    let symbol = String::from_str(&env, "wXLM");
+    client.initialize_wrapped(&admin, &name, &symbol, &underlying.address);
+
+    // Should panic
+    client.mint(&admin, &1_000);
+}
+
+#[test]
+fn test_approve_transfer_from_and_burn() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let user = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    client.approve(&admin, &spender, &3_000);
+    assert_eq!(client.allowance(&admin, &spender), 3_000);
+
+    client.transfer_from(&spender, &admin, &user, &1_000);
+    assert_eq!(client.balance(&user), 1_000);
+    assert_eq!(client.allowance(&admin, &spender), 2_000);
+    assert!(client.try_transfer_from(&spender, &admin, &user, &2_001).is_err());
+
+    client.burn(&user, &400);
+    assert_eq!(client.balance(&user), 600);
+    assert_eq!(client.get_total_supply(), 999_600);
+}
+
+#[test]
+fn test_spend_limit_rolling_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let other = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+    client.transfer(&admin, &owner, &100_000);
+
+    assert_eq!(client.remaining_limit(&owner), None);
+    client.set_spend_limit(&owner, &owner, &Some(10_000));
+    assert_eq!(client.remaining_limit(&owner), Some(10_000));
+
+    // transfer, transfer_from and burn all count against the limit
+    let start = env.ledger().timestamp();
+    client.transfer(&owner, &other, &4_000);
+    client.approve(&owner, &spender, &50_000);
+    env.ledger().with_mut(|li| {
+        li.timestamp = start + 3_600;
+    });
+    client.transfer_from(&spender, &owner, &other, &3_000);
+    client.burn(&owner, &2_000);
+    assert_eq!(client.remaining_limit(&owner), Some(1_000));
+    assert!(client.try_transfer(&owner, &other, &1_001).is_err());
+
+    // 24h after the first transfer only that transfer falls out of the window
+    env.ledger().with_mut(|li| {
+        li.timestamp = start + LIMIT_WINDOW;
+    });
+    assert_eq!(client.remaining_limit(&owner), Some(5_000));
+    client.transfer(&owner, &other, &5_000);
+    assert_eq!(client.remaining_limit(&owner), Some(0));
+
+    // Removing the limit lifts the guardrail
+    client.set_spend_limit(&owner, &owner, &None);
+    client.transfer(&owner, &other, &20_000);
+    assert_eq!(client.remaining_limit(&owner), None);
+}
+
+#[test]
+#[should_panic(expected = "Limit dikelola admin")]
+fn test_spend_limit_managed_by_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let custodial = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+
+    client.set_spend_limit(&admin, &custodial, &Some(1_000));
+    assert!(client.get_spend_limit(&custodial).unwrap().managed);
+
+    // Should panic: the owner cannot lift an admin-managed limit
+    client.set_spend_limit(&custodial, &custodial, &None);
+}
+
+#[test]
+#[should_panic(expected = "Limit harian terlampaui")]
+fn test_spend_limit_blocks_htlc_lock() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let client = setup_token(&env, &admin, 1_000_000);
+    client.transfer(&admin, &owner, &100_000);
+    client.set_spend_limit(&admin, &owner, &Some(1_000));
+
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = env.ledger().timestamp() + 3600;
+
+    // Should panic: escrowing tokens is an outflow too
+    client.lock(&owner, &admin, &5_000, &hashlock, &timelock);
+}
+
+// Property-based invariants: random call sequences across many addresses,
+// checked against a simple reference model after every call
+mod invariants {
+    use super::*;
+    use proptest::prelude::*;
+    use soroban_sdk::testutils::EnvTestConfig;
+    use std::collections::BTreeMap;
+
+    const ACCOUNTS: usize = 5;
+
+    #[derive(Clone, Debug)]
+    enum Op {
+        Initialize { admin: usize, supply: i128 },
+        Mint { to: usize, amount: i128 },
+        Transfer { from: usize, to: usize, amount: i128 },
+        Approve { from: usize, spender: usize, amount: i128 },
+        TransferFrom { spender: usize, from: usize, to: usize, amount: i128 },
+        Burn { from: usize, amount: i128 },
+    }
+
+    // Mostly valid amounts, with zero/negative amounts and overspends mixed in
+    fn amount() -> impl Strategy<Value = i128> {
+        prop_oneof![
+            1 => -100i128..=0,
+            6 => 1i128..=1_000,
+            2 => 1_000i128..=20_000,
+        ]
+    }
+
+    fn account() -> impl Strategy<Value = usize> {
+        0..ACCOUNTS
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            1 => (account(), amount())
+                .prop_map(|(admin, supply)| Op::Initialize { admin, supply }),
+            2 => (account(), amount())
+                .prop_map(|(to, amount)| Op::Mint { to, amount }),
+            4 => (account(), account(), amount())
+                .prop_map(|(from, to, amount)| Op::Transfer { from, to, amount }),
+            3 => (account(), account(), amount())
+                .prop_map(|(from, spender, amount)| Op::Approve { from, spender, amount }),
+            3 => (account(), account(), account(), amount())
+                .prop_map(|(spender, from, to, amount)| Op::TransferFrom { spender, from, to, amount }),
+            2 => (account(), amount())
+                .prop_map(|(from, amount)| Op::Burn { from, amount }),
+        ]
+    }
+
+    // What the ledger should look like after the same sequence of calls
+    #[derive(Debug, Default)]
+    struct Model {
+        initialized: bool,
+        total: i128,
+        balances: [i128; ACCOUNTS],
+        allowances: BTreeMap<(usize, usize), i128>,
+    }
+
+    impl Model {
+        fn allowance(&self, from: usize, spender: usize) -> i128 {
+            self.allowances.get(&(from, spender)).copied().unwrap_or(0)
+        }
+
+        // Apply `op` and return whether the contract should accept it.
+        // A rejected op leaves the model untouched.
+        fn apply(&mut self, op: &Op) -> bool {
+            match *op {
+                Op::Initialize { admin, supply } => {
+                    if self.initialized || supply <= 0 {
+                        return false;
+                    }
+                    self.initialized = true;
+                    self.total = supply;
+                    self.balances[admin] += supply;
+                }
+                Op::Mint { to, amount } => {
+                    if !self.initialized || amount <= 0 {
+                        return false;
+                    }
+                    self.balances[to] += amount;
+                    self.total += amount;
+                }
+                Op::Transfer { from, to, amount } => {
+                    if amount <= 0 || self.balances[from] < amount {
+                        return false;
+                    }
+                    self.balances[from] -= amount;
+                    self.balances[to] += amount;
+                }
+                Op::Approve { from, spender, amount } => {
+                    if amount < 0 {
+                        return false;
+                    }
+                    self.allowances.insert((from, spender), amount);
+                }
+                Op::TransferFrom { spender, from, to, amount } => {
+                    let allowance = self.allowance(from, spender);
+                    if amount <= 0 || allowance < amount || self.balances[from] < amount {
+                        return false;
+                    }
+                    self.allowances.insert((from, spender), allowance - amount);
+                    self.balances[from] -= amount;
+                    self.balances[to] += amount;
+                }
+                Op::Burn { from, amount } => {
+                    if amount <= 0 || self.balances[from] < amount {
+                        return false;
+                    }
+                    self.balances[from] -= amount;
+                    self.total -= amount;
+                }
+            }
+            true
+        }
+    }
+
+    // Everything observable about the ledger, read from the contract
+    #[derive(Debug, PartialEq)]
+    struct LedgerState {
+        total: i128,
+        balances: std::vec::Vec<i128>,
+        // Indexed by from * ACCOUNTS + spender
+        allowances: std::vec::Vec<i128>,
+        holder_count: u32,
+    }
+
+    fn read_state(client: &TokenContractClient, accounts: &[Address]) -> LedgerState {
+        LedgerState {
+            // No supply exists before initialize
+            total: client.try_get_total_supply().map_or(0, |total| total.unwrap()),
+            balances: accounts.iter().map(|id| client.balance(id)).collect(),
+            allowances: accounts
+                .iter()
+                .flat_map(|from| accounts.iter().map(move |spender| (from, spender)))
+                .map(|(from, spender)| client.allowance(from, spender))
+                .collect(),
+            holder_count: client.holder_count(),
+        }
+    }
+
+    // Run `op` against the contract, return whether it succeeded
+    fn call(env: &Env, client: &TokenContractClient, accounts: &[Address], op: &Op) -> bool {
+        match *op {
+            Op::Initialize { admin, supply } => client
+                .try_initialize(
+                    &accounts[admin],
+                    &String::from_str(env, "Property Token"),
+                    &String::from_str(env, "PROP"),
+                    &supply,
+                )
+                .is_ok(),
+            Op::Mint { to, amount } => client.try_mint(&accounts[to], &amount).is_ok(),
+            Op::Transfer { from, to, amount } => client
+                .try_transfer(&accounts[from], &accounts[to], &amount)
+                .is_ok(),
+            Op::Approve { from, spender, amount } => client
+                .try_approve(&accounts[from], &accounts[spender], &amount)
+                .is_ok(),
+            Op::TransferFrom { spender, from, to, amount } => client
+                .try_transfer_from(&accounts[spender], &accounts[from], &accounts[to], &amount)
+                .is_ok(),
+            Op::Burn { from, amount } => client.try_burn(&accounts[from], &amount).is_ok(),
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn prop_ledger_invariants(ops in prop::collection::vec(op(), 1..30)) {
+            // Skip snapshots, every case builds a fresh Env
+            let env = Env::new_with_config(EnvTestConfig { capture_snapshot_at_drop: false });
+            env.mock_all_auths();
+
+            let client = TokenContractClient::new(&env, &env.register(TokenContract, ()));
+            let accounts: std::vec::Vec<Address> =
+                (0..ACCOUNTS).map(|_| Address::generate(&env)).collect();
+            let mut model = Model::default();
+            let mut before = read_state(&client, &accounts);
+
+            for op in ops.iter() {
+                let expected = model.apply(op);
+                let succeeded = call(&env, &client, &accounts, op);
+                let after = read_state(&client, &accounts);
+
+                prop_assert_eq!(succeeded, expected, "unexpected result for {:?}", op);
+
+                // Failed calls leave state untouched
+                if !succeeded {
+                    prop_assert_eq!(&after, &before, "failed {:?} changed state", op);
+                }
+
+                // No negative balances
+                prop_assert!(after.balances.iter().all(|balance| *balance >= 0));
+
+                // Supply conservation: every token is held by someone
+                prop_assert_eq!(after.balances.iter().sum::<i128>(), after.total);
+
+                // Allowances only grow through approve, and transfer_from
+                // spends exactly the amount moved
+                for (i, (old, new)) in before.allowances.iter().zip(&after.allowances).enumerate() {
+                    let pair = (i / ACCOUNTS, i % ACCOUNTS);
+                    match *op {
+                        Op::Approve { from, spender, amount }
+                            if succeeded && pair == (from, spender) =>
+                        {
+                            prop_assert_eq!(*new, amount);
+                        }
+                        Op::TransferFrom { spender, from, amount, .. }
+                            if succeeded && pair == (from, spender) =>
+                        {
+                            prop_assert_eq!(*new, old - amount);
+                        }
+                        _ => prop_assert_eq!(new, old),
+                    }
+                }
+
+                // Contract agrees with the reference model
+                prop_assert_eq!(&after.balances[..], &model.balances[..]);
+                prop_assert_eq!(after.total, model.total);
+                prop_assert_eq!(
+                    after.holder_count as usize,
+                    model.balances.iter().filter(|balance| **balance > 0).count()
+                );
+
+                before = after;
+            }
+        }
+    }
+}
@@ -61,10 +61,10 @@ impl SnakeGameCompetition {
         }
 
         // Check active competition
-        if let Some(c) = env.storage().instance().get::<Symbol, Competition>(&COMPETITION) {
-            if c.status == STATUS_ACTIVE {
-                panic!("Competition already active");
-            }
+        if let Some(c) = env.storage().instance().get::<Symbol, Competition>(&COMPETITION)
+            && c.status == STATUS_ACTIVE
+        {
+            panic!("Competition already active");
         }
 
         let now = env.ledger().timestamp();
@@ -229,7 +229,7 @@ impl SnakeGameCompetition {
         let lb: Vec<PlayerScore> = env.storage().instance().get(&LEADERBOARD).unwrap_or(Vec::new(&env));
         let prize_pool = comp.prize_pool;
 
-        if prize_pool > 0 && lb.len() > 0 {
+        if prize_pool > 0 && !lb.is_empty() {
             let token_addr: Address = env.storage().instance().get(&TOKEN).unwrap();
             let token_client = token::Client::new(&env, &token_addr);
             let contract_addr = env.current_contract_address();
@@ -243,7 +243,7 @@ impl SnakeGameCompetition {
             let remaining_pool = prize_pool - admin_fee;
 
             // Rank 1: 50% of remaining
-            if lb.len() >= 1 {
+            if !lb.is_empty() {
                 let p = lb.get(0).unwrap();
                 let amt = (remaining_pool * 50) / 100;
                 token_client.transfer(&contract_addr, &p.player, &amt);